@@ -0,0 +1,44 @@
+use std::borrow::Cow;
+
+use base64::{engine::general_purpose::URL_SAFE, Engine};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use serde::{Deserialize, Deserializer};
+
+#[derive(Deserialize)]
+struct Borrowed {
+    #[serde(with = "serde_repr_base64::base64")]
+    _data: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct Owned {
+    #[serde(deserialize_with = "deserialize_owned")]
+    _data: Vec<u8>,
+}
+
+/// Decoding through an intermediate `String`, as `base64` did previously.
+fn deserialize_owned<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let s = <Cow<str>>::deserialize(deserializer)?;
+    URL_SAFE
+        .decode(s.as_bytes())
+        .map_err(serde::de::Error::custom)
+}
+
+fn deserialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deserialize");
+    for size in [1 << 10, 1 << 16, 1 << 22] {
+        let data: Vec<u8> = (0..size).map(|x| x as u8).collect();
+        let json = format!(r#"{{"_data":"{}"}}"#, URL_SAFE.encode(&data));
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::new("borrowed", size), &json, |b, json| {
+            b.iter(|| serde_json::from_str::<Borrowed>(json).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("owned", size), &json, |b, json| {
+            b.iter(|| serde_json::from_str::<Owned>(json).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, deserialize);
+criterion_main!(benches);
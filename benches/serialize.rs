@@ -0,0 +1,45 @@
+use base64::{engine::general_purpose::URL_SAFE, Engine};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use serde::{Serialize, Serializer};
+
+#[derive(Serialize)]
+struct Streamed<'t> {
+    #[serde(with = "serde_repr_base64::base64")]
+    data: &'t [u8],
+}
+
+#[derive(Serialize)]
+struct Buffered<'t> {
+    #[serde(serialize_with = "serialize_buffered")]
+    data: &'t [u8],
+}
+
+/// Encoding into an intermediate `String`, as `base64` did previously.
+fn serialize_buffered<S: Serializer>(data: &&[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&URL_SAFE.encode(data))
+}
+
+fn serialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialize");
+    for size in [1 << 10, 1 << 16, 1 << 22] {
+        let data: Vec<u8> = (0..size).map(|x| x as u8).collect();
+        let mut output = Vec::with_capacity(size * 2);
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::new("streamed", size), &data, |b, data| {
+            b.iter(|| {
+                output.clear();
+                serde_json::to_writer(&mut output, &Streamed { data }).unwrap()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("buffered", size), &data, |b, data| {
+            b.iter(|| {
+                output.clear();
+                serde_json::to_writer(&mut output, &Buffered { data }).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, serialize);
+criterion_main!(benches);
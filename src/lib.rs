@@ -4,138 +4,4336 @@
 //!
 //! * [base64] and [base64_if_readable]
 //!
-//! [Arrays](std::array), [Vec] and your favorite small vec crates like [SmallVec](http://crates.io/crates/smallvec).
+//! [Arrays](core::array), [Vec][alloc::vec::Vec] and your favorite small vec crates like [SmallVec](http://crates.io/crates/smallvec).
+//!
+//! * [base64_le] and [base64_be]
+//!
+//! Same as [base64] but with a fixed byte order, so the output is portable across machines.
 //!
 //! * [base64_string]
 //!
 //! [String] and your favorite small string crates like [SmolStr](http://crates.io/crates/smol_str).
+//!
+//! * `option`, `seq` and `map_values`
+//!
+//! Each of [base64], [base64_if_readable] and [base64_string] has these submodules
+//! to encode the elements of `Option<T>`, collections like `Vec<T>` and the values of maps.
+//!
+//! * [base64::map_keys] and [Base64Key]
+//!
+//! Byte array keys like `HashMap<[u8; 32], V>`, encoded as `base64` in human readable formats.
+//!
+//! * [Base64] and [Base64String]
+//!
+//! Wrappers with the same representation as [base64] and [base64_string],
+//! for generics, collections like `Vec<Base64<T>>` and types you cannot annotate.
+//!
+//! * [hex], [hex_if_readable] and [hex_string]
+//!
+//! The same as the `base64` modules, but with lower or upper case `hex` and an optional `0x` prefix.
+//!
+//! * [base32], [base32_if_readable] and [base32_string]
+//!
+//! The same as the `base64` modules, with the RFC 4648, base32hex and Crockford alphabets.
+//!
+//! * `base58` and `base58_if_readable`
+//!
+//! The same as [base64] and [base64_if_readable], with the Bitcoin, Ripple and Flickr alphabets
+//! and Base58Check. This requires the `base58` feature.
+//!
+//! * [z85] and [ascii85]
+//!
+//! The same as [base64], but with 25% overhead instead of 33%.
+//!
+//! * [base45]
+//!
+//! The same as [base64], but with symbols that fit the alphanumeric mode of QR codes.
+//!
+//! * `bech32`
+//!
+//! Bech32 and Bech32m strings with a human readable part chosen per field, like `npub1...`.
+//! This requires the `bech32` feature.
+//!
+//! * [multibase]
+//!
+//! Self describing strings prefixed by a [multibase](https://github.com/multiformats/multibase) code,
+//! any supported encoding is accepted when deserializing.
+//!
+//! # Engines
+//!
+//! By default the `base64` modules use the [`URL_SAFE`](::base64::engine::general_purpose::URL_SAFE) engine.
+//! To use a different alphabet or padding, use the `With` adaptor in each module
+//! with one of the engines in [`engine`], or your own implementation of [`Base64Engine`].
+//!
+//! The other encodings have their own defaults, listed in the documentation of each module,
+//! and their own codecs for the `With` adaptor, like [`hex::Upper`] or [`base32::Crockford`].
+//!
+//! ```
+//! # use serde::{Serialize, Deserialize};
+//! use serde_repr_base64::{base64, engine::StandardNoPad};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Signed {
+//!     #[serde(with = "base64::With::<StandardNoPad>")]
+//!     signature: [u8; 32],
+//! }
+//! ```
+//!
+//! # Codecs
+//!
+//! The `With` adaptors accept any [`Codec`], every [`Base64Engine`] is one.
+//! Implement [`Codec`] to use your own encoding with the generic adaptors in [`codec`],
+//! which have the same representation and combinators as the `base64` modules.
+//! Codecs that cannot encode every input reject it in [`Codec::validate`],
+//! which serializing reports as an [`EncodeError`].
+//!
+//! # Features
+//!
+//! * `std` (default)
+//!
+//! Enables `alloc` and runtime CPU detection for `simd`.
+//!
+//! * `alloc`
+//!
+//! Without `alloc`, serializing with [base64], [hex], [base32], [z85], [ascii85], [base45], [multibase]
+//! and `bech32`, and the `array` adaptor of each of them like [`base64::array`] and [`hex::array`]
+//! are available, both encode and decode on the stack.
+//! Everything else works on `no_std` with `alloc`.
+//!
+//! * `simd`
+//!
+//! Use [`base64_simd`](https://crates.io/crates/base64-simd) for the engines in [`engine`],
+//! with runtime CPU detection and a scalar fallback. The output is identical,
+//! custom engines always use the scalar implementation.
+//!
+//! * `base58`
+//!
+//! Enables `alloc` and the `base58` modules, using [`bs58`](https://crates.io/crates/bs58).
+//!
+//! * `bech32`
+//!
+//! Enables the `bech32` module, using [`bech32`](https://crates.io/crates/bech32).
+#![cfg_attr(
+    not(feature = "alloc"),
+    doc = "
+
+[base64_if_readable]: crate#features
+[base64_le]: crate#features
+[base64_be]: crate#features
+[base64_string]: crate#features
+[base64::map_keys]: crate#features
+[hex_if_readable]: crate#features
+[hex_string]: crate#features
+[base32_if_readable]: crate#features
+[base32_string]: crate#features
+[Base64Key]: crate#features
+[Base64String]: crate#features
+[String]: crate#features
+[alloc::vec::Vec]: #features"
+)]
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+pub use codec::Codec;
+pub use engine::Base64Engine;
+pub use wrapper::Base64;
+#[cfg(feature = "alloc")]
+pub use wrapper::{Base64Key, Base64String};
+
+/// Engines usable with the `With` adaptors.
+pub mod engine {
+    pub use base64::alphabet::{self, Alphabet};
+    pub use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+    pub use base64::engine::DecodePaddingMode;
+
+    use base64::engine::general_purpose::{NO_PAD, PAD};
+
+    /// Selects the [`GeneralPurpose`] engine used by a `With` adaptor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use serde_repr_base64::engine::{alphabet, Base64Engine, GeneralPurpose, GeneralPurposeConfig};
+    ///
+    /// pub struct Crypt;
+    ///
+    /// impl Base64Engine for Crypt {
+    ///     const ENGINE: GeneralPurpose = GeneralPurpose::new(&alphabet::CRYPT, GeneralPurposeConfig::new());
+    /// }
+    /// ```
+    pub trait Base64Engine {
+        const ENGINE: GeneralPurpose;
+
+        /// The `base64_simd` equivalent of [`Base64Engine::ENGINE`], if there is one.
+        #[cfg(feature = "simd")]
+        #[doc(hidden)]
+        const SIMD: Option<base64_simd::Base64> = None;
+    }
+
+    /// The standard alphabet with padding.
+    pub struct Standard;
+
+    /// The standard alphabet without padding.
+    pub struct StandardNoPad;
+
+    /// The url safe alphabet with padding, this is the default.
+    pub struct UrlSafe;
+
+    /// The url safe alphabet without padding.
+    pub struct UrlSafeNoPad;
+
+    impl Base64Engine for Standard {
+        const ENGINE: GeneralPurpose = GeneralPurpose::new(&alphabet::STANDARD, PAD);
+        #[cfg(feature = "simd")]
+        const SIMD: Option<base64_simd::Base64> = Some(base64_simd::STANDARD);
+    }
+
+    impl Base64Engine for StandardNoPad {
+        const ENGINE: GeneralPurpose = GeneralPurpose::new(&alphabet::STANDARD, NO_PAD);
+        #[cfg(feature = "simd")]
+        const SIMD: Option<base64_simd::Base64> = Some(base64_simd::STANDARD_NO_PAD);
+    }
+
+    impl Base64Engine for UrlSafe {
+        const ENGINE: GeneralPurpose = GeneralPurpose::new(&alphabet::URL_SAFE, PAD);
+        #[cfg(feature = "simd")]
+        const SIMD: Option<base64_simd::Base64> = Some(base64_simd::URL_SAFE);
+    }
+
+    impl Base64Engine for UrlSafeNoPad {
+        const ENGINE: GeneralPurpose = GeneralPurpose::new(&alphabet::URL_SAFE, NO_PAD);
+        #[cfg(feature = "simd")]
+        const SIMD: Option<base64_simd::Base64> = Some(base64_simd::URL_SAFE_NO_PAD);
+    }
+}
+
+/// The [`Codec`] trait and the generic adaptors used by every encoding in this crate.
+///
+/// [`bytes`](codec::bytes), [`bytes_if_readable`][codec::bytes_if_readable] and [`string`][codec::string]
+/// have the same representation as [base64], [base64_if_readable] and [base64_string],
+/// but with a [`Codec`] chosen by their `With` adaptors.
+///
+/// # Example
+///
+/// ```
+/// use core::fmt::Write;
+/// # use serde::{Serialize, Deserialize};
+/// use serde_repr_base64::{codec::{self, Codec}, DecodeError};
+///
+/// /// Three octal digits per byte.
+/// pub struct Octal;
+///
+/// impl Codec for Octal {
+///     fn decoded_len_estimate(input: &[u8]) -> usize {
+///         input.len() / 3
+///     }
+///
+///     fn encode<W: Write>(bytes: &[u8], out: &mut W) -> core::fmt::Result {
+///         bytes.iter().try_for_each(|byte| write!(out, "{byte:03o}"))
+///     }
+///
+///     fn decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+///         let len = input.len() / 3;
+///         if input.len() % 3 != 0 || len > output.len() {
+///             return Err(DecodeError::InvalidEncodedLength { len: input.len() });
+///         }
+///         for (index, chunk) in input.chunks(3).enumerate() {
+///             let digits = core::str::from_utf8(chunk).ok();
+///             output[index] = digits
+///                 .and_then(|digits| u8::from_str_radix(digits, 8).ok())
+///                 .ok_or(DecodeError::InvalidSymbol { offset: index * 3 })?;
+///         }
+///         Ok(len)
+///     }
+/// }
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Mask {
+///     #[serde(with = "codec::bytes::With::<Octal>")]
+///     bytes: Vec<u8>,
+/// }
+///
+/// let json = serde_json::to_string(&Mask { bytes: vec![7, 255] }).unwrap();
+/// assert_eq!(json, r#"{"bytes":"007377"}"#);
+/// ```
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "
+
+[codec::bytes_if_readable]: crate#features
+[codec::string]: crate#features
+[base64_if_readable]: crate#features
+[base64_string]: #features"
+)]
+pub mod codec {
+    use core::fmt::Write;
+
+    use crate::backend;
+    use crate::{Base64Engine, DecodeError, EncodeError};
+
+    /// A text encoding for bytes, used by the `With` adaptors.
+    ///
+    /// Every [`Base64Engine`] is a codec.
+    ///
+    /// When [`Codec::validate`] accepts `bytes`, [`Codec::encode`] must succeed for them
+    /// unless the writer fails, the adaptors rely on this to never fail while formatting.
+    pub trait Codec {
+        /// An upper bound of the encoded length of `len` bytes, or `None` if it is not known.
+        ///
+        /// This is an optional hint for callers that allocate the output, the adaptors do not use it.
+        fn encoded_len(len: usize) -> Option<usize> {
+            let _ = len;
+            None
+        }
+
+        /// An upper bound of the decoded length of `input`.
+        fn decoded_len_estimate(input: &[u8]) -> usize;
+
+        /// The decoded length of `input` if it is valid, or `None` if it is not known.
+        ///
+        /// This is used to reject fixed size arrays of the wrong length before decoding,
+        /// codecs whose length depends on the value can find it by decoding into a scratch buffer.
+        fn decoded_len(input: &[u8]) -> Option<usize> {
+            let _ = input;
+            None
+        }
+
+        /// Check that `bytes` can be encoded, the adaptors call this before [`Codec::encode`].
+        ///
+        /// Codecs that cannot encode every input, for example because of a maximum length,
+        /// report it here so serializing returns an error.
+        fn validate(bytes: &[u8]) -> Result<(), EncodeError> {
+            let _ = bytes;
+            Ok(())
+        }
+
+        /// Write `bytes` as text to `out`.
+        ///
+        /// This must only return an error when `out` does, since serializers may panic
+        /// when formatting fails on its own. Reject inputs in [`Codec::validate`] instead.
+        fn encode<W: Write>(bytes: &[u8], out: &mut W) -> core::fmt::Result;
+
+        /// Decode `input` into `output` and return the number of bytes written.
+        ///
+        /// `output` has at least [`Codec::decoded_len_estimate`] bytes when decoding into a buffer,
+        /// but can be smaller when decoding into a fixed size array,
+        /// in which case [`DecodeError::InvalidEncodedLength`] should be returned.
+        fn decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError>;
+    }
+
+    impl<E: Base64Engine> Codec for E {
+        fn encoded_len(len: usize) -> Option<usize> {
+            backend::encoded_len::<E>(len)
+        }
+
+        fn decoded_len_estimate(input: &[u8]) -> usize {
+            base64::decoded_len_estimate(input.len())
+        }
+
+        fn decoded_len(input: &[u8]) -> Option<usize> {
+            let symbols = input.iter().rposition(|x| *x != b'=').map_or(0, |x| x + 1);
+            Some(symbols * 3 / 4)
+        }
+
+        fn encode<W: Write>(bytes: &[u8], out: &mut W) -> core::fmt::Result {
+            backend::encode::<E, W>(bytes, out)
+        }
+
+        fn decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+            backend::decode::<E>(input, output)
+        }
+    }
+
+    /// A `#[serde(with)]` adaptor that converts an array into an encoded string, see [base64](crate::base64).
+    ///
+    /// Use [`With`](bytes::With) to choose the [`Codec`].
+    pub mod bytes {
+        use core::{borrow::Borrow, marker::PhantomData};
+
+        use crate::backend::collect_encoded;
+        use bytemuck::NoUninit;
+        use serde::Serializer;
+
+        use crate::codec::Codec;
+
+        #[cfg(feature = "alloc")]
+        use alloc::vec::Vec;
+        #[cfg(feature = "alloc")]
+        use bytemuck::AnyBitPattern;
+        #[cfg(feature = "alloc")]
+        use core::fmt::Display;
+        #[cfg(feature = "alloc")]
+        use serde::{de::Visitor, Deserializer};
+
+        #[cfg(feature = "alloc")]
+        use crate::combinator::{De, Ser};
+        #[cfg(feature = "alloc")]
+        use crate::typed::{copy_typed, decode_typed};
+        #[cfg(feature = "alloc")]
+        use crate::DecodeError;
+
+        /// A `#[serde(with = "codec::bytes::With::<E>")]` adaptor using [`Codec`] `E`.
+        pub struct With<E: Codec>(PhantomData<E>);
+
+        impl<E: Codec> With<E> {
+            #[doc(hidden)]
+            pub fn serialize<S: Serializer, T: Borrow<[U]>, U: NoUninit>(
+                item: &T,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                let slice: &[u8] = bytemuck::cast_slice(item.borrow());
+                collect_encoded::<E, _>(slice, serializer)
+            }
+
+            #[doc(hidden)]
+            #[cfg(feature = "alloc")]
+            pub fn deserialize<
+                'de,
+                D: Deserializer<'de>,
+                T: for<'t> TryFrom<&'t [U], Error: Display>,
+                U: AnyBitPattern,
+            >(
+                deserializer: D,
+            ) -> Result<T, D::Error> {
+                let decoded = deserializer.deserialize_str(EncodedVisitor::<E, U>(PhantomData))?;
+                DecodeError::convert(decoded.as_slice()).map_err(serde::de::Error::custom)
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        struct EncodedVisitor<E, U>(PhantomData<(E, U)>);
+
+        #[cfg(feature = "alloc")]
+        impl<'de, E: Codec, U: AnyBitPattern> Visitor<'de> for EncodedVisitor<E, U> {
+            type Value = Vec<U>;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                formatter.write_str("an encoded string")
+            }
+
+            fn visit_str<Er: serde::de::Error>(self, v: &str) -> Result<Self::Value, Er> {
+                decode_typed::<E, U>(v.as_bytes()).map_err(Er::custom)
+            }
+
+            fn visit_bytes<Er: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, Er> {
+                copy_typed(v).map_err(Er::custom)
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<T: Borrow<[U]>, U: NoUninit, E: Codec> serde::Serialize for Ser<'_, With<E>, T, U> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                With::<E>::serialize(self.0, serializer)
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'de, T, U, E> serde::Deserialize<'de> for De<With<E>, T, U>
+        where
+            T: for<'t> TryFrom<&'t [U], Error: Display>,
+            U: AnyBitPattern,
+            E: Codec,
+        {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                With::<E>::deserialize(deserializer).map(De::new)
+            }
+        }
+
+        /// A `#[serde(with)]` adaptor for `Option<T>`, that writes the same encoded string
+        /// as [bytes](crate::codec::bytes) if the value is present.
+        ///
+        /// This requires the `alloc` feature.
+        #[cfg(feature = "alloc")]
+        pub mod option {
+            use core::{borrow::Borrow, fmt::Display, marker::PhantomData};
+
+            use bytemuck::{AnyBitPattern, NoUninit};
+            use serde::{Deserializer, Serializer};
+
+            use crate::codec::Codec;
+            use crate::combinator;
+
+            /// A `#[serde(with = "codec::bytes::option::With::<E>")]` adaptor using [`Codec`] `E`.
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, T: Borrow<[U]>, U: NoUninit>(
+                    item: &Option<T>,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    combinator::serialize_option::<_, super::With<E>, T, U>(item, serializer)
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<
+                    'de,
+                    D: Deserializer<'de>,
+                    T: for<'t> TryFrom<&'t [U], Error: Display>,
+                    U: AnyBitPattern,
+                >(
+                    deserializer: D,
+                ) -> Result<Option<T>, D::Error> {
+                    combinator::deserialize_option::<_, super::With<E>, T, U>(deserializer)
+                }
+            }
+        }
+
+        /// A `#[serde(with)]` adaptor for collections of `T` like `Vec<T>`, `VecDeque<T>` or `BTreeSet<T>`,
+        /// that writes a sequence of the same encoded strings as [bytes](crate::codec::bytes).
+        ///
+        /// This supports collections `C` where `&C` iterates over `&T` and `C` implements [`FromIterator<T>`].
+        /// Fixed size arrays `[T; N]` do not implement [`FromIterator<T>`] and are not supported.
+        ///
+        /// This requires the `alloc` feature.
+        #[cfg(feature = "alloc")]
+        pub mod seq {
+            use core::{borrow::Borrow, fmt::Display, marker::PhantomData};
+
+            use bytemuck::{AnyBitPattern, NoUninit};
+            use serde::{Deserializer, Serializer};
+
+            use crate::codec::Codec;
+            use crate::combinator;
+
+            /// A `#[serde(with = "codec::bytes::seq::With::<E>")]` adaptor using [`Codec`] `E`.
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, C, T: Borrow<[U]>, U: NoUninit>(
+                    items: &C,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error>
+                where
+                    for<'t> &'t C: IntoIterator<Item = &'t T>,
+                {
+                    combinator::serialize_seq::<_, super::With<E>, C, T, U>(items, serializer)
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<
+                    'de,
+                    D: Deserializer<'de>,
+                    C: FromIterator<T>,
+                    T: for<'t> TryFrom<&'t [U], Error: Display>,
+                    U: AnyBitPattern,
+                >(
+                    deserializer: D,
+                ) -> Result<C, D::Error> {
+                    combinator::deserialize_seq::<_, super::With<E>, C, T, U>(deserializer)
+                }
+            }
+        }
+
+        /// A `#[serde(with)]` adaptor for maps like `HashMap<K, T>` or `BTreeMap<K, T>`,
+        /// that writes the values as the same encoded strings as [bytes](crate::codec::bytes).
+        ///
+        /// This supports maps `M` where `&M` iterates over `(&K, &T)` and `M` implements [`FromIterator<(K, T)>`].
+        /// The keys use their own [`Serialize`](serde::Serialize) and [`Deserialize`](serde::Deserialize) implementations.
+        ///
+        /// This requires the `alloc` feature.
+        #[cfg(feature = "alloc")]
+        pub mod map_values {
+            use core::{borrow::Borrow, fmt::Display, marker::PhantomData};
+
+            use bytemuck::{AnyBitPattern, NoUninit};
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            use crate::codec::Codec;
+            use crate::combinator::{self, Plain};
+
+            /// A `#[serde(with = "codec::bytes::map_values::With::<E>")]` adaptor using [`Codec`] `E`.
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, M, K: Serialize, T: Borrow<[U]>, U: NoUninit>(
+                    items: &M,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error>
+                where
+                    for<'t> &'t M: IntoIterator<Item = (&'t K, &'t T)>,
+                {
+                    combinator::serialize_map::<_, M, K, T, Plain, (), super::With<E>, U>(
+                        items, serializer,
+                    )
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<
+                    'de,
+                    D: Deserializer<'de>,
+                    M: FromIterator<(K, T)>,
+                    K: Deserialize<'de>,
+                    T: for<'t> TryFrom<&'t [U], Error: Display>,
+                    U: AnyBitPattern,
+                >(
+                    deserializer: D,
+                ) -> Result<M, D::Error> {
+                    combinator::deserialize_map::<_, M, K, T, Plain, (), super::With<E>, U>(
+                        deserializer,
+                    )
+                }
+            }
+        }
+
+        /// A `#[serde(with)]` adaptor for maps like `HashMap<K, V>` or `BTreeMap<K, V>`,
+        /// that writes the keys as the same encoded strings as [bytes](crate::codec::bytes)
+        /// in human readable formats like `json`, so byte arrays can be used as keys.
+        ///
+        /// In binary formats the keys use their own [`Serialize`](serde::Serialize) implementation.
+        /// The values always use their own implementations.
+        ///
+        /// This supports maps `M` where `&M` iterates over `(&K, &V)` and `M` implements [`FromIterator<(K, V)>`],
+        /// `K` needs to implement [`Borrow<[T]>`](core::borrow::Borrow) and [`TryFrom<&[T]>`](core::convert::TryFrom)
+        /// in addition to [`Serialize`](serde::Serialize) and [`Deserialize`](serde::Deserialize).
+        /// Use [`Base64Key`](crate::Base64Key) for keys in other places.
+        ///
+        /// This requires the `alloc` feature.
+        #[cfg(feature = "alloc")]
+        pub mod map_keys {
+            use core::{borrow::Borrow, fmt::Display, marker::PhantomData};
+
+            use bytemuck::{AnyBitPattern, NoUninit};
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            use crate::codec::Codec;
+            use crate::combinator::{self, De, Plain, Ser};
+
+            /// A `#[serde(with = "codec::bytes::map_keys::With::<E>")]` adaptor using [`Codec`] `E`.
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, M, K, V: Serialize, U: NoUninit>(
+                    items: &M,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error>
+                where
+                    for<'t> &'t M: IntoIterator<Item = (&'t K, &'t V)>,
+                    K: Borrow<[U]> + Serialize,
+                {
+                    combinator::serialize_map::<_, M, K, V, With<E>, U, Plain, ()>(
+                        items, serializer,
+                    )
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<
+                    'de,
+                    D: Deserializer<'de>,
+                    M: FromIterator<(K, V)>,
+                    K: for<'t> TryFrom<&'t [U], Error: Display> + Deserialize<'de>,
+                    V: Deserialize<'de>,
+                    U: AnyBitPattern,
+                >(
+                    deserializer: D,
+                ) -> Result<M, D::Error> {
+                    combinator::deserialize_map::<_, M, K, V, With<E>, U, Plain, ()>(deserializer)
+                }
+            }
+
+            /// Serialize a single key, shared with [`Base64Key`](crate::Base64Key).
+            pub(crate) fn serialize_key<S: Serializer, E: Codec, K, U: NoUninit>(
+                key: &K,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error>
+            where
+                K: Borrow<[U]> + Serialize,
+            {
+                if serializer.is_human_readable() {
+                    super::With::<E>::serialize(key, serializer)
+                } else {
+                    key.serialize(serializer)
+                }
+            }
+
+            /// Deserialize a single key, shared with [`Base64Key`](crate::Base64Key).
+            pub(crate) fn deserialize_key<'de, D: Deserializer<'de>, E: Codec, K, U>(
+                deserializer: D,
+            ) -> Result<K, D::Error>
+            where
+                K: for<'t> TryFrom<&'t [U], Error: Display> + Deserialize<'de>,
+                U: AnyBitPattern,
+            {
+                if deserializer.is_human_readable() {
+                    super::With::<E>::deserialize(deserializer)
+                } else {
+                    K::deserialize(deserializer)
+                }
+            }
+
+            impl<K: Borrow<[U]> + Serialize, U: NoUninit, E: Codec> Serialize for Ser<'_, With<E>, K, U> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serialize_key::<S, E, K, U>(self.0, serializer)
+                }
+            }
+
+            impl<'de, K, U, E> Deserialize<'de> for De<With<E>, K, U>
+            where
+                K: for<'t> TryFrom<&'t [U], Error: Display> + Deserialize<'de>,
+                U: AnyBitPattern,
+                E: Codec,
+            {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserialize_key::<D, E, K, U>(deserializer).map(De::new)
+                }
+            }
+        }
+
+        /// A `#[serde(with)]` adaptor that writes the same encoded string as [bytes](crate::codec::bytes),
+        /// but reads encoded strings, byte strings or sequences of `T` in self-describing formats.
+        ///
+        /// This is useful when migrating a field stored as bytes or as a sequence
+        /// to [bytes](crate::codec::bytes). This requires `T` to implement [`Deserialize`](serde::Deserialize)
+        /// and does not work with formats that are not self-describing like `postcard`.
+        ///
+        /// This requires the `alloc` feature.
+        #[cfg(feature = "alloc")]
+        pub mod lenient {
+            use alloc::vec::Vec;
+            use core::{borrow::Borrow, fmt::Display, marker::PhantomData};
+
+            use bytemuck::{AnyBitPattern, NoUninit};
+            use serde::{de::Visitor, Deserialize, Deserializer, Serializer};
+
+            use super::EncodedVisitor;
+            use crate::codec::Codec;
+            use crate::DecodeError;
+
+            /// A `#[serde(with = "codec::bytes::lenient::With::<E>")]` adaptor using [`Codec`] `E`.
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, T: Borrow<[U]>, U: NoUninit>(
+                    item: &T,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    super::With::<E>::serialize(item, serializer)
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<
+                    'de,
+                    D: Deserializer<'de>,
+                    T: for<'t> TryFrom<&'t [U], Error: Display>,
+                    U: AnyBitPattern + Deserialize<'de>,
+                >(
+                    deserializer: D,
+                ) -> Result<T, D::Error> {
+                    let decoded =
+                        deserializer.deserialize_any(LenientVisitor::<E, U>(PhantomData))?;
+                    DecodeError::convert(decoded.as_slice()).map_err(serde::de::Error::custom)
+                }
+            }
+
+            struct LenientVisitor<E, U>(PhantomData<(E, U)>);
+
+            impl<'de, E: Codec, U: AnyBitPattern + Deserialize<'de>> Visitor<'de> for LenientVisitor<E, U> {
+                type Value = Vec<U>;
+
+                fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                    formatter.write_str("an encoded string, a byte string or a sequence")
+                }
+
+                fn visit_str<Er: serde::de::Error>(self, v: &str) -> Result<Self::Value, Er> {
+                    EncodedVisitor::<E, U>(PhantomData).visit_str(v)
+                }
+
+                fn visit_bytes<Er: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, Er> {
+                    EncodedVisitor::<E, U>(PhantomData).visit_bytes(v)
+                }
+
+                fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                    self,
+                    mut seq: A,
+                ) -> Result<Self::Value, A::Error> {
+                    let mut result = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+                    while let Some(item) = seq.next_element()? {
+                        result.push(item);
+                    }
+                    Ok(result)
+                }
+            }
+        }
+        /// A `#[serde(with)]` adaptor for owned buffers,
+        /// that writes the same encoded string as [bytes](crate::codec::bytes).
+        ///
+        /// This supports types that implement [`Borrow<[T]>`](core::borrow::Borrow) and [`TryFrom<Vec<T>>`](core::convert::TryFrom),
+        /// like [`Vec`], [`Box<[T]>`](alloc::boxed::Box) and [`Arc<[T]>`](alloc::sync::Arc).
+        /// The decoded buffer is moved into the target instead of being copied.
+        ///
+        /// This requires the `alloc` feature.
+        #[cfg(feature = "alloc")]
+        pub mod owned {
+            use alloc::{format, vec::Vec};
+            use core::{any::type_name, borrow::Borrow, marker::PhantomData};
+
+            use bytemuck::{AnyBitPattern, NoUninit};
+            use serde::{Deserializer, Serializer};
+
+            use super::EncodedVisitor;
+            use crate::codec::Codec;
+            use crate::DecodeError;
+
+            /// A `#[serde(with = "codec::bytes::owned::With::<E>")]` adaptor using [`Codec`] `E`.
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, T: Borrow<[U]>, U: NoUninit>(
+                    item: &T,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    super::With::<E>::serialize(item, serializer)
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<
+                    'de,
+                    D: Deserializer<'de>,
+                    T: TryFrom<Vec<U>>,
+                    U: AnyBitPattern,
+                >(
+                    deserializer: D,
+                ) -> Result<T, D::Error> {
+                    let decoded =
+                        deserializer.deserialize_str(EncodedVisitor::<E, U>(PhantomData))?;
+                    let len = decoded.len();
+                    T::try_from(decoded).map_err(|_| {
+                        serde::de::Error::custom(DecodeError::Conversion(format!(
+                            "cannot convert {len} elements into {}",
+                            type_name::<T>()
+                        )))
+                    })
+                }
+            }
+        }
+
+        /// A `#[serde(with)]` adaptor for fixed size arrays `[T; N]`,
+        /// that writes the same encoded string as [bytes](crate::codec::bytes).
+        ///
+        /// This decodes directly into the array, validates the length before decoding,
+        /// and does not allocate.
+        pub mod array {
+            use core::marker::PhantomData;
+
+            use bytemuck::{AnyBitPattern, NoUninit};
+            use serde::{de::Visitor, Deserializer, Serializer};
+
+            use crate::codec::Codec;
+            use crate::typed::{copy_array, decode_array};
+
+            /// A `#[serde(with = "codec::bytes::array::With::<E>")]` adaptor using [`Codec`] `E`.
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, U: NoUninit, const N: usize>(
+                    item: &[U; N],
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    super::With::<E>::serialize(item, serializer)
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<'de, D: Deserializer<'de>, U: AnyBitPattern, const N: usize>(
+                    deserializer: D,
+                ) -> Result<[U; N], D::Error> {
+                    deserializer.deserialize_str(ArrayVisitor::<E, U, N>(PhantomData))
+                }
+            }
+
+            struct ArrayVisitor<E, U, const N: usize>(PhantomData<(E, U)>);
+
+            impl<'de, E: Codec, U: AnyBitPattern, const N: usize> Visitor<'de> for ArrayVisitor<E, U, N> {
+                type Value = [U; N];
+
+                fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                    formatter.write_str("an encoded string")
+                }
+
+                fn visit_str<Er: serde::de::Error>(self, v: &str) -> Result<Self::Value, Er> {
+                    decode_array::<E, U, N>(v.as_bytes()).map_err(Er::custom)
+                }
+
+                fn visit_bytes<Er: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, Er> {
+                    copy_array(v).map_err(Er::custom)
+                }
+            }
+        }
+    }
+
+    /// A `#[serde(with)]` adaptor that converts an array into an encoded string only
+    /// in human readable formats, see [base64_if_readable](crate::base64_if_readable).
+    ///
+    /// Use [`With`](bytes_if_readable::With) to choose the [`Codec`].
+    ///
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub mod bytes_if_readable {
+        use alloc::vec::Vec;
+        use core::{borrow::Borrow, fmt::Display, marker::PhantomData};
+
+        use crate::backend::collect_encoded;
+        use bytemuck::{AnyBitPattern, NoUninit};
+        use serde::{de::Visitor, Deserialize, Deserializer, Serializer};
+
+        use crate::codec::Codec;
+        use crate::combinator::{De, Ser};
+        use crate::typed::{copy_typed, decode_typed};
+        use crate::visitor::StrVisitor;
+        use crate::DecodeError;
+
+        /// A `#[serde(with = "codec::bytes_if_readable::With::<E>")]` adaptor using [`Codec`] `E`.
+        pub struct With<E: Codec>(PhantomData<E>);
+
+        impl<E: Codec> With<E> {
+            #[doc(hidden)]
+            pub fn serialize<S: Serializer, T: Borrow<[U]>, U: NoUninit>(
+                item: &T,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                let slice: &[u8] = bytemuck::cast_slice(item.borrow());
+                if serializer.is_human_readable() {
+                    collect_encoded::<E, _>(slice, serializer)
+                } else {
+                    serializer.serialize_bytes(slice)
+                }
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<
+                'de,
+                D: Deserializer<'de>,
+                T: for<'t> TryFrom<&'t [U], Error: Display>,
+                U: AnyBitPattern + Deserialize<'de>,
+            >(
+                deserializer: D,
+            ) -> Result<T, D::Error> {
+                let decoded = if deserializer.is_human_readable() {
+                    deserializer
+                        .deserialize_str(StrVisitor::new(|s| decode_typed::<E, U>(s.as_bytes())))?
+                } else {
+                    deserializer.deserialize_bytes(BytesVisitor(PhantomData))?
+                };
+                DecodeError::convert(decoded.as_slice()).map_err(serde::de::Error::custom)
+            }
+        }
+
+        struct BytesVisitor<U>(PhantomData<U>);
+
+        impl<'de, U: AnyBitPattern + Deserialize<'de>> Visitor<'de> for BytesVisitor<U> {
+            type Value = Vec<U>;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                formatter.write_str("a byte string")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                copy_typed(v).map_err(E::custom)
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut result = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+                while let Some(item) = seq.next_element()? {
+                    result.push(item);
+                }
+                Ok(result)
+            }
+        }
+
+        impl<T: Borrow<[U]>, U: NoUninit, E: Codec> serde::Serialize for Ser<'_, With<E>, T, U> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                With::<E>::serialize(self.0, serializer)
+            }
+        }
+
+        impl<'de, T, U, E> serde::Deserialize<'de> for De<With<E>, T, U>
+        where
+            T: for<'t> TryFrom<&'t [U], Error: Display>,
+            U: AnyBitPattern + Deserialize<'de>,
+            E: Codec,
+        {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                With::<E>::deserialize(deserializer).map(De::new)
+            }
+        }
+
+        /// A `#[serde(with)]` adaptor for `Option<T>`, that writes the same representation
+        /// as [bytes_if_readable](crate::codec::bytes_if_readable) if the value is present.
+        pub mod option {
+            use core::{borrow::Borrow, fmt::Display, marker::PhantomData};
+
+            use bytemuck::{AnyBitPattern, NoUninit};
+            use serde::{Deserialize, Deserializer, Serializer};
+
+            use crate::codec::Codec;
+            use crate::combinator;
+
+            /// A `#[serde(with = "codec::bytes_if_readable::option::With::<E>")]` adaptor using [`Codec`] `E`.
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, T: Borrow<[U]>, U: NoUninit>(
+                    item: &Option<T>,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    combinator::serialize_option::<_, super::With<E>, T, U>(item, serializer)
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<
+                    'de,
+                    D: Deserializer<'de>,
+                    T: for<'t> TryFrom<&'t [U], Error: Display>,
+                    U: AnyBitPattern + Deserialize<'de>,
+                >(
+                    deserializer: D,
+                ) -> Result<Option<T>, D::Error> {
+                    combinator::deserialize_option::<_, super::With<E>, T, U>(deserializer)
+                }
+            }
+        }
+
+        /// A `#[serde(with)]` adaptor for collections of `T` like `Vec<T>`, `VecDeque<T>` or `BTreeSet<T>`,
+        /// that writes a sequence of the same representation as [bytes_if_readable](crate::codec::bytes_if_readable).
+        ///
+        /// This supports collections `C` where `&C` iterates over `&T` and `C` implements [`FromIterator<T>`].
+        /// Fixed size arrays `[T; N]` do not implement [`FromIterator<T>`] and are not supported.
+        pub mod seq {
+            use core::{borrow::Borrow, fmt::Display, marker::PhantomData};
+
+            use bytemuck::{AnyBitPattern, NoUninit};
+            use serde::{Deserialize, Deserializer, Serializer};
+
+            use crate::codec::Codec;
+            use crate::combinator;
+
+            /// A `#[serde(with = "codec::bytes_if_readable::seq::With::<E>")]` adaptor using [`Codec`] `E`.
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, C, T: Borrow<[U]>, U: NoUninit>(
+                    items: &C,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error>
+                where
+                    for<'t> &'t C: IntoIterator<Item = &'t T>,
+                {
+                    combinator::serialize_seq::<_, super::With<E>, C, T, U>(items, serializer)
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<
+                    'de,
+                    D: Deserializer<'de>,
+                    C: FromIterator<T>,
+                    T: for<'t> TryFrom<&'t [U], Error: Display>,
+                    U: AnyBitPattern + Deserialize<'de>,
+                >(
+                    deserializer: D,
+                ) -> Result<C, D::Error> {
+                    combinator::deserialize_seq::<_, super::With<E>, C, T, U>(deserializer)
+                }
+            }
+        }
+
+        /// A `#[serde(with)]` adaptor for maps like `HashMap<K, T>` or `BTreeMap<K, T>`,
+        /// that writes the values in the same representation as [bytes_if_readable](crate::codec::bytes_if_readable).
+        ///
+        /// This supports maps `M` where `&M` iterates over `(&K, &T)` and `M` implements [`FromIterator<(K, T)>`].
+        /// The keys use their own [`Serialize`](serde::Serialize) and [`Deserialize`] implementations.
+        pub mod map_values {
+            use core::{borrow::Borrow, fmt::Display, marker::PhantomData};
+
+            use bytemuck::{AnyBitPattern, NoUninit};
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            use crate::codec::Codec;
+            use crate::combinator::{self, Plain};
+
+            /// A `#[serde(with = "codec::bytes_if_readable::map_values::With::<E>")]` adaptor using [`Codec`] `E`.
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, M, K: Serialize, T: Borrow<[U]>, U: NoUninit>(
+                    items: &M,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error>
+                where
+                    for<'t> &'t M: IntoIterator<Item = (&'t K, &'t T)>,
+                {
+                    combinator::serialize_map::<_, M, K, T, Plain, (), super::With<E>, U>(
+                        items, serializer,
+                    )
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<
+                    'de,
+                    D: Deserializer<'de>,
+                    M: FromIterator<(K, T)>,
+                    K: Deserialize<'de>,
+                    T: for<'t> TryFrom<&'t [U], Error: Display>,
+                    U: AnyBitPattern + Deserialize<'de>,
+                >(
+                    deserializer: D,
+                ) -> Result<M, D::Error> {
+                    combinator::deserialize_map::<_, M, K, T, Plain, (), super::With<E>, U>(
+                        deserializer,
+                    )
+                }
+            }
+        }
+
+        /// A `#[serde(with)]` adaptor that writes a sequence of `T` in binary formats
+        /// instead of a byte string, this is the format used by previous versions of [bytes_if_readable](crate::codec::bytes_if_readable)
+        /// for `Vec<T>` and other collections.
+        ///
+        /// Previous versions wrote fixed size arrays `[T; N]` as a tuple without a length,
+        /// use [`array`](legacy::array) to read and write those.
+        ///
+        /// This requires `T` to implement [`Serialize`](serde::Serialize) and [`Deserialize`].
+        pub mod legacy {
+            use alloc::vec::Vec;
+            use core::{borrow::Borrow, fmt::Display, marker::PhantomData};
+
+            use crate::backend::collect_encoded;
+            use bytemuck::{AnyBitPattern, NoUninit};
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            use crate::codec::Codec;
+            use crate::typed::decode_typed;
+            use crate::visitor::StrVisitor;
+            use crate::DecodeError;
+
+            /// A `#[serde(with = "codec::bytes_if_readable::legacy::With::<E>")]` adaptor using [`Codec`] `E`.
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, T: Borrow<[U]>, U: NoUninit + Serialize>(
+                    item: &T,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    if serializer.is_human_readable() {
+                        let slice: &[u8] = bytemuck::cast_slice(item.borrow());
+                        collect_encoded::<E, _>(slice, serializer)
+                    } else {
+                        serializer.collect_seq(item.borrow())
+                    }
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<
+                    'de,
+                    D: Deserializer<'de>,
+                    T: for<'t> TryFrom<&'t [U], Error: Display>,
+                    U: AnyBitPattern + Deserialize<'de>,
+                >(
+                    deserializer: D,
+                ) -> Result<T, D::Error> {
+                    let decoded = if deserializer.is_human_readable() {
+                        deserializer.deserialize_str(StrVisitor::new(|s| {
+                            decode_typed::<E, U>(s.as_bytes())
+                        }))?
+                    } else {
+                        Vec::<U>::deserialize(deserializer)?
+                    };
+                    DecodeError::convert(decoded.as_slice()).map_err(serde::de::Error::custom)
+                }
+            }
+
+            /// A `#[serde(with)]` adaptor for fixed size arrays `[T; N]` that writes a tuple of `T`
+            /// in binary formats, this is the format used by previous versions of [bytes_if_readable](crate::codec::bytes_if_readable)
+            /// for arrays.
+            ///
+            /// Human readable formats use the same encoded string as [bytes](crate::codec::bytes).
+            pub mod array {
+                use core::marker::PhantomData;
+
+                use bytemuck::{AnyBitPattern, NoUninit};
+                use serde::de::{Error, SeqAccess, Visitor};
+                use serde::ser::SerializeTuple;
+                use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+                use crate::codec::Codec;
+                use crate::DecodeError;
+
+                /// A `#[serde(with = "codec::bytes_if_readable::legacy::array::With::<E>")]` adaptor using [`Codec`] `E`.
+                pub struct With<E: Codec>(PhantomData<E>);
+
+                impl<E: Codec> With<E> {
+                    #[doc(hidden)]
+                    pub fn serialize<S: Serializer, U: NoUninit + Serialize, const N: usize>(
+                        item: &[U; N],
+                        serializer: S,
+                    ) -> Result<S::Ok, S::Error> {
+                        if serializer.is_human_readable() {
+                            crate::codec::bytes::With::<E>::serialize(item, serializer)
+                        } else {
+                            let mut tuple = serializer.serialize_tuple(N)?;
+                            for element in item {
+                                tuple.serialize_element(element)?;
+                            }
+                            tuple.end()
+                        }
+                    }
+
+                    #[doc(hidden)]
+                    pub fn deserialize<
+                        'de,
+                        D: Deserializer<'de>,
+                        U: AnyBitPattern + Deserialize<'de>,
+                        const N: usize,
+                    >(
+                        deserializer: D,
+                    ) -> Result<[U; N], D::Error> {
+                        if deserializer.is_human_readable() {
+                            crate::codec::bytes::array::With::<E>::deserialize(deserializer)
+                        } else {
+                            deserializer.deserialize_tuple(N, TupleVisitor(PhantomData))
+                        }
+                    }
+                }
+
+                struct TupleVisitor<U, const N: usize>(PhantomData<U>);
+
+                impl<'de, U: AnyBitPattern + Deserialize<'de>, const N: usize> Visitor<'de> for TupleVisitor<U, N> {
+                    type Value = [U; N];
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                        write!(formatter, "a tuple of {N} elements")
+                    }
+
+                    fn visit_seq<A: SeqAccess<'de>>(
+                        self,
+                        mut seq: A,
+                    ) -> Result<Self::Value, A::Error> {
+                        let mut array = [U::zeroed(); N];
+                        for (got, element) in array.iter_mut().enumerate() {
+                            *element = seq.next_element()?.ok_or_else(|| {
+                                A::Error::custom(DecodeError::WrongLength { expected: N, got })
+                            })?;
+                        }
+                        Ok(array)
+                    }
+                }
+            }
+        }
+    }
+
+    /// A `#[serde(with)]` adaptor that converts a string into an encoded string,
+    /// see [base64_string](crate::base64_string).
+    ///
+    /// Use [`With`](string::With) to choose the [`Codec`].
+    ///
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub mod string {
+        use alloc::string::String;
+        use core::{fmt::Display, marker::PhantomData};
+
+        use crate::backend::collect_encoded;
+        use serde::{Deserializer, Serializer};
+
+        use crate::codec::Codec;
+        use crate::combinator::{De, Ser};
+        use crate::typed::decode_typed;
+        use crate::visitor::StrVisitor;
+        use crate::DecodeError;
+
+        /// A `#[serde(with = "codec::string::With::<E>")]` adaptor using [`Codec`] `E`.
+        pub struct With<E: Codec>(PhantomData<E>);
+
+        impl<E: Codec> With<E> {
+            #[doc(hidden)]
+            pub fn serialize<S: Serializer, T: AsRef<str>>(
+                item: &T,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                collect_encoded::<E, _>(item.as_ref().as_bytes(), serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D: Deserializer<'de>, T: TryFrom<String, Error: Display>>(
+                deserializer: D,
+            ) -> Result<T, D::Error> {
+                let string = deserializer.deserialize_str(StrVisitor::new(|s| {
+                    String::from_utf8(decode_typed::<E, u8>(s.as_bytes())?).map_err(|e| {
+                        DecodeError::InvalidUtf8 {
+                            offset: e.utf8_error().valid_up_to(),
+                        }
+                    })
+                }))?;
+                T::try_from(string)
+                    .map_err(|e| serde::de::Error::custom(DecodeError::conversion(e)))
+            }
+        }
+
+        impl<T: AsRef<str>, E: Codec> serde::Serialize for Ser<'_, With<E>, T> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                With::<E>::serialize(self.0, serializer)
+            }
+        }
+
+        impl<'de, T: TryFrom<String, Error: Display>, E: Codec> serde::Deserialize<'de> for De<With<E>, T> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                With::<E>::deserialize(deserializer).map(De::new)
+            }
+        }
+
+        /// A `#[serde(with)]` adaptor for `Option<T>`, that writes the same encoded string
+        /// as [string](crate::codec::string) if the value is present.
+        pub mod option {
+            use alloc::string::String;
+            use core::{fmt::Display, marker::PhantomData};
+
+            use serde::{Deserializer, Serializer};
+
+            use crate::codec::Codec;
+            use crate::combinator;
+
+            /// A `#[serde(with = "codec::string::option::With::<E>")]` adaptor using [`Codec`] `E`.
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, T: AsRef<str>>(
+                    item: &Option<T>,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    combinator::serialize_option::<_, super::With<E>, T, ()>(item, serializer)
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<
+                    'de,
+                    D: Deserializer<'de>,
+                    T: TryFrom<String, Error: Display>,
+                >(
+                    deserializer: D,
+                ) -> Result<Option<T>, D::Error> {
+                    combinator::deserialize_option::<_, super::With<E>, T, ()>(deserializer)
+                }
+            }
+        }
+
+        /// A `#[serde(with)]` adaptor for collections of strings like `Vec<T>`, `VecDeque<T>` or `BTreeSet<T>`,
+        /// that writes a sequence of the same encoded strings as [string](crate::codec::string).
+        ///
+        /// This supports collections `C` where `&C` iterates over `&T` and `C` implements [`FromIterator<T>`].
+        /// Fixed size arrays `[T; N]` do not implement [`FromIterator<T>`] and are not supported.
+        pub mod seq {
+            use alloc::string::String;
+            use core::{fmt::Display, marker::PhantomData};
+
+            use serde::{Deserializer, Serializer};
+
+            use crate::codec::Codec;
+            use crate::combinator;
+
+            /// A `#[serde(with = "codec::string::seq::With::<E>")]` adaptor using [`Codec`] `E`.
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, C, T: AsRef<str>>(
+                    items: &C,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error>
+                where
+                    for<'t> &'t C: IntoIterator<Item = &'t T>,
+                {
+                    combinator::serialize_seq::<_, super::With<E>, C, T, ()>(items, serializer)
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<
+                    'de,
+                    D: Deserializer<'de>,
+                    C: FromIterator<T>,
+                    T: TryFrom<String, Error: Display>,
+                >(
+                    deserializer: D,
+                ) -> Result<C, D::Error> {
+                    combinator::deserialize_seq::<_, super::With<E>, C, T, ()>(deserializer)
+                }
+            }
+        }
+
+        /// A `#[serde(with)]` adaptor for maps like `HashMap<K, T>` or `BTreeMap<K, T>`,
+        /// that writes the values as the same encoded strings as [string](crate::codec::string).
+        ///
+        /// This supports maps `M` where `&M` iterates over `(&K, &T)` and `M` implements [`FromIterator<(K, T)>`].
+        /// The keys use their own [`Serialize`](serde::Serialize) and [`Deserialize`](serde::Deserialize) implementations.
+        pub mod map_values {
+            use alloc::string::String;
+            use core::{fmt::Display, marker::PhantomData};
+
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            use crate::codec::Codec;
+            use crate::combinator::{self, Plain};
+
+            /// A `#[serde(with = "codec::string::map_values::With::<E>")]` adaptor using [`Codec`] `E`.
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, M, K: Serialize, T: AsRef<str>>(
+                    items: &M,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error>
+                where
+                    for<'t> &'t M: IntoIterator<Item = (&'t K, &'t T)>,
+                {
+                    combinator::serialize_map::<_, M, K, T, Plain, (), super::With<E>, ()>(
+                        items, serializer,
+                    )
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<
+                    'de,
+                    D: Deserializer<'de>,
+                    M: FromIterator<(K, T)>,
+                    K: Deserialize<'de>,
+                    T: TryFrom<String, Error: Display>,
+                >(
+                    deserializer: D,
+                ) -> Result<M, D::Error> {
+                    combinator::deserialize_map::<_, M, K, T, Plain, (), super::With<E>, ()>(
+                        deserializer,
+                    )
+                }
+            }
+        }
+    }
+}
+
+/// Error returned when decoding fails.
+///
+/// The error never contains the input, since it might be a secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input contains a symbol not in the alphabet, or a non-canonical final symbol.
+    InvalidSymbol {
+        /// Byte offset of the symbol in the input.
+        offset: usize,
+    },
+    /// The padding is missing, excessive or not allowed by the engine.
+    InvalidPadding,
+    /// The number of symbols in the input cannot be produced by any encoding.
+    InvalidEncodedLength {
+        /// Number of symbols in the input.
+        len: usize,
+    },
+    /// The number of decoded bytes is not a multiple of the element size.
+    InvalidLength {
+        /// Number of decoded bytes.
+        len: usize,
+        /// Size of an element in bytes.
+        element_size: usize,
+    },
+    /// The number of decoded elements does not match the length of a fixed size target.
+    WrongLength {
+        /// Length of the target.
+        expected: usize,
+        /// Number of decoded elements.
+        got: usize,
+    },
+    /// A decoded element is not representable by the element type.
+    OutOfRange {
+        /// Index of the element.
+        index: usize,
+    },
+    /// The input does not start with the expected prefix or human readable part.
+    InvalidPrefix,
+    /// The checksum of the input does not match the decoded bytes.
+    InvalidChecksum,
+    /// The decoded bytes are not valid utf-8.
+    InvalidUtf8 {
+        /// Byte offset of the first invalid byte in the decoded bytes.
+        offset: usize,
+    },
+    /// The target type rejected the decoded value.
+    ///
+    /// The message names the number of decoded elements and the target type.
+    #[cfg(feature = "alloc")]
+    Conversion(String),
+}
+
+#[cfg(feature = "alloc")]
+impl DecodeError {
+    pub(crate) fn conversion(error: impl core::fmt::Display) -> Self {
+        DecodeError::Conversion(error.to_string())
+    }
+
+    /// Converts the decoded elements into `T`, naming the number of elements and `T` on failure.
+    pub(crate) fn convert<T, U>(decoded: &[U]) -> Result<T, Self>
+    where
+        T: for<'t> TryFrom<&'t [U], Error: core::fmt::Display>,
+    {
+        T::try_from(decoded).map_err(|e| {
+            DecodeError::Conversion(format!(
+                "cannot convert {} elements into {}: {e}",
+                decoded.len(),
+                core::any::type_name::<T>()
+            ))
+        })
+    }
+}
+
+impl From<::base64::DecodeError> for DecodeError {
+    fn from(value: ::base64::DecodeError) -> Self {
+        match value {
+            ::base64::DecodeError::InvalidByte(offset, _)
+            | ::base64::DecodeError::InvalidLastSymbol(offset, _) => {
+                DecodeError::InvalidSymbol { offset }
+            }
+            ::base64::DecodeError::InvalidLength(len) => DecodeError::InvalidEncodedLength { len },
+            ::base64::DecodeError::InvalidPadding => DecodeError::InvalidPadding,
+        }
+    }
+}
+
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecodeError::InvalidSymbol { offset } => {
+                write!(f, "invalid symbol at offset {offset}")
+            }
+            DecodeError::InvalidPadding => f.write_str("invalid padding"),
+            DecodeError::InvalidEncodedLength { len } => {
+                write!(f, "invalid encoded length {len}")
+            }
+            DecodeError::InvalidLength { len, element_size } => write!(
+                f,
+                "decoded length {len} is not a multiple of element size {element_size}"
+            ),
+            DecodeError::WrongLength { expected, got } => {
+                write!(f, "expected {expected} elements, got {got}")
+            }
+            DecodeError::OutOfRange { index } => {
+                write!(f, "element {index} is out of range for the element type")
+            }
+            DecodeError::InvalidPrefix => f.write_str("invalid prefix"),
+            DecodeError::InvalidChecksum => f.write_str("invalid checksum"),
+            DecodeError::InvalidUtf8 { offset } => {
+                write!(f, "decoded bytes are not valid utf-8 at offset {offset}")
+            }
+            #[cfg(feature = "alloc")]
+            DecodeError::Conversion(error) => write!(f, "conversion failed: {error}"),
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+/// Error returned when bytes cannot be encoded by a [`Codec`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EncodeError {
+    /// The encoded string would be longer than the codec allows.
+    TooLong {
+        /// Length of the encoded string.
+        len: usize,
+        /// Maximum length allowed by the codec.
+        max: usize,
+    },
+    /// The prefix or human readable part of the codec is invalid.
+    InvalidPrefix,
+}
+
+impl core::fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            EncodeError::TooLong { len, max } => {
+                write!(f, "encoded length {len} exceeds the maximum of {max}")
+            }
+            EncodeError::InvalidPrefix => f.write_str("invalid prefix"),
+        }
+    }
+}
+
+impl core::error::Error for EncodeError {}
+
+/// Generates the default `serialize` and `deserialize` functions and the combinators
+/// of a module with the representation of [`codec::bytes`], using the codec `$codec`.
+macro_rules! bytes_module {
+    ($name: literal, $codec: ty) => {
+        pub use $crate::codec::bytes::With;
+
+        #[doc(hidden)]
+        pub fn serialize<S, T, U>(item: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ::serde::Serializer,
+            T: ::core::borrow::Borrow<[U]>,
+            U: ::bytemuck::NoUninit,
+        {
+            With::<$codec>::serialize(item, serializer)
+        }
+
+        #[doc(hidden)]
+        #[cfg(feature = "alloc")]
+        pub fn deserialize<'de, D, T, U>(deserializer: D) -> Result<T, D::Error>
+        where
+            D: ::serde::Deserializer<'de>,
+            T: for<'t> TryFrom<&'t [U], Error: ::core::fmt::Display>,
+            U: ::bytemuck::AnyBitPattern,
+        {
+            With::<$codec>::deserialize(deserializer)
+        }
+
+        #[doc = concat!("Writes `Option<T>` as a `", $name, "` string if the value is present.")]
+        ///
+        /// See [`codec::bytes::option`](crate::codec::bytes::option).
+        #[cfg(feature = "alloc")]
+        pub mod option {
+            pub use $crate::codec::bytes::option::With;
+
+            #[doc(hidden)]
+            pub fn serialize<S, T, U>(item: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+                T: ::core::borrow::Borrow<[U]>,
+                U: ::bytemuck::NoUninit,
+            {
+                With::<$codec>::serialize(item, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D, T, U>(deserializer: D) -> Result<Option<T>, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                T: for<'t> TryFrom<&'t [U], Error: ::core::fmt::Display>,
+                U: ::bytemuck::AnyBitPattern,
+            {
+                With::<$codec>::deserialize(deserializer)
+            }
+        }
+
+        #[doc = concat!("Writes a collection of `T` as a sequence of `", $name, "` strings.")]
+        ///
+        /// See [`codec::bytes::seq`](crate::codec::bytes::seq).
+        #[cfg(feature = "alloc")]
+        pub mod seq {
+            pub use $crate::codec::bytes::seq::With;
+
+            #[doc(hidden)]
+            pub fn serialize<S, C, T, U>(items: &C, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+                for<'t> &'t C: IntoIterator<Item = &'t T>,
+                T: ::core::borrow::Borrow<[U]>,
+                U: ::bytemuck::NoUninit,
+            {
+                With::<$codec>::serialize(items, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D, C, T, U>(deserializer: D) -> Result<C, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                C: FromIterator<T>,
+                T: for<'t> TryFrom<&'t [U], Error: ::core::fmt::Display>,
+                U: ::bytemuck::AnyBitPattern,
+            {
+                With::<$codec>::deserialize(deserializer)
+            }
+        }
+
+        #[doc = concat!("Writes the values of a map as `", $name, "` strings.")]
+        ///
+        /// See [`codec::bytes::map_values`](crate::codec::bytes::map_values).
+        #[cfg(feature = "alloc")]
+        pub mod map_values {
+            pub use $crate::codec::bytes::map_values::With;
+
+            #[doc(hidden)]
+            pub fn serialize<S, M, K, T, U>(items: &M, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+                for<'t> &'t M: IntoIterator<Item = (&'t K, &'t T)>,
+                K: ::serde::Serialize,
+                T: ::core::borrow::Borrow<[U]>,
+                U: ::bytemuck::NoUninit,
+            {
+                With::<$codec>::serialize(items, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D, M, K, T, U>(deserializer: D) -> Result<M, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                M: FromIterator<(K, T)>,
+                K: ::serde::Deserialize<'de>,
+                T: for<'t> TryFrom<&'t [U], Error: ::core::fmt::Display>,
+                U: ::bytemuck::AnyBitPattern,
+            {
+                With::<$codec>::deserialize(deserializer)
+            }
+        }
+
+        #[doc = concat!("Writes the keys of a map as `", $name, "` strings in human readable formats.")]
+        ///
+        /// See [`codec::bytes::map_keys`](crate::codec::bytes::map_keys).
+        #[cfg(feature = "alloc")]
+        pub mod map_keys {
+            pub use $crate::codec::bytes::map_keys::With;
+
+            #[doc(hidden)]
+            pub fn serialize<S, M, K, V, U>(items: &M, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+                for<'t> &'t M: IntoIterator<Item = (&'t K, &'t V)>,
+                K: ::core::borrow::Borrow<[U]> + ::serde::Serialize,
+                V: ::serde::Serialize,
+                U: ::bytemuck::NoUninit,
+            {
+                With::<$codec>::serialize(items, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D, M, K, V, U>(deserializer: D) -> Result<M, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                M: FromIterator<(K, V)>,
+                K: for<'t> TryFrom<&'t [U], Error: ::core::fmt::Display> + ::serde::Deserialize<'de>,
+                V: ::serde::Deserialize<'de>,
+                U: ::bytemuck::AnyBitPattern,
+            {
+                With::<$codec>::deserialize(deserializer)
+            }
+        }
+
+        #[doc = concat!("Writes a `", $name, "` string, but also reads byte strings and sequences.")]
+        ///
+        /// See [`codec::bytes::lenient`](crate::codec::bytes::lenient).
+        #[cfg(feature = "alloc")]
+        pub mod lenient {
+            pub use $crate::codec::bytes::lenient::With;
+
+            #[doc(hidden)]
+            pub fn serialize<S, T, U>(item: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+                T: ::core::borrow::Borrow<[U]>,
+                U: ::bytemuck::NoUninit,
+            {
+                With::<$codec>::serialize(item, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D, T, U>(deserializer: D) -> Result<T, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                T: for<'t> TryFrom<&'t [U], Error: ::core::fmt::Display>,
+                U: ::bytemuck::AnyBitPattern + ::serde::Deserialize<'de>,
+            {
+                With::<$codec>::deserialize(deserializer)
+            }
+        }
+
+        #[doc = concat!("Writes a `", $name, "` string and moves the decoded buffer into the target.")]
+        ///
+        /// See [`codec::bytes::owned`](crate::codec::bytes::owned).
+        #[cfg(feature = "alloc")]
+        pub mod owned {
+            pub use $crate::codec::bytes::owned::With;
+
+            #[doc(hidden)]
+            pub fn serialize<S, T, U>(item: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+                T: ::core::borrow::Borrow<[U]>,
+                U: ::bytemuck::NoUninit,
+            {
+                With::<$codec>::serialize(item, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D, T, U>(deserializer: D) -> Result<T, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                T: TryFrom<::alloc::vec::Vec<U>>,
+                U: ::bytemuck::AnyBitPattern,
+            {
+                With::<$codec>::deserialize(deserializer)
+            }
+        }
+
+        #[doc = concat!("Writes a fixed size array as a `", $name, "` string and decodes it without allocating.")]
+        ///
+        /// See [`codec::bytes::array`](crate::codec::bytes::array).
+        pub mod array {
+            pub use $crate::codec::bytes::array::With;
+
+            #[doc(hidden)]
+            pub fn serialize<S, U, const N: usize>(
+                item: &[U; N],
+                serializer: S,
+            ) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+                U: ::bytemuck::NoUninit,
+            {
+                With::<$codec>::serialize(item, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D, U, const N: usize>(deserializer: D) -> Result<[U; N], D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                U: ::bytemuck::AnyBitPattern,
+            {
+                With::<$codec>::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+/// Generates the default `serialize` and `deserialize` functions and the combinators
+/// of a module with the representation of [`codec::bytes_if_readable`], using the codec `$codec`.
+#[cfg(feature = "alloc")]
+macro_rules! bytes_if_readable_module {
+    ($name: literal, $codec: ty) => {
+        pub use $crate::codec::bytes_if_readable::With;
+
+        #[doc(hidden)]
+        pub fn serialize<S, T, U>(item: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ::serde::Serializer,
+            T: ::core::borrow::Borrow<[U]>,
+            U: ::bytemuck::NoUninit,
+        {
+            With::<$codec>::serialize(item, serializer)
+        }
+
+        #[doc(hidden)]
+        pub fn deserialize<'de, D, T, U>(deserializer: D) -> Result<T, D::Error>
+        where
+            D: ::serde::Deserializer<'de>,
+            T: for<'t> TryFrom<&'t [U], Error: ::core::fmt::Display>,
+            U: ::bytemuck::AnyBitPattern + ::serde::Deserialize<'de>,
+        {
+            With::<$codec>::deserialize(deserializer)
+        }
+
+        #[doc = concat!("Writes `Option<T>` as a `", $name, "` string in human readable formats if the value is present.")]
+        ///
+        /// See [`codec::bytes_if_readable::option`](crate::codec::bytes_if_readable::option).
+        pub mod option {
+            pub use $crate::codec::bytes_if_readable::option::With;
+
+            #[doc(hidden)]
+            pub fn serialize<S, T, U>(item: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+                T: ::core::borrow::Borrow<[U]>,
+                U: ::bytemuck::NoUninit,
+            {
+                With::<$codec>::serialize(item, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D, T, U>(deserializer: D) -> Result<Option<T>, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                T: for<'t> TryFrom<&'t [U], Error: ::core::fmt::Display>,
+                U: ::bytemuck::AnyBitPattern + ::serde::Deserialize<'de>,
+            {
+                With::<$codec>::deserialize(deserializer)
+            }
+        }
+
+        #[doc = concat!("Writes a collection of `T` as a sequence of `", $name, "` strings in human readable formats.")]
+        ///
+        /// See [`codec::bytes_if_readable::seq`](crate::codec::bytes_if_readable::seq).
+        pub mod seq {
+            pub use $crate::codec::bytes_if_readable::seq::With;
+
+            #[doc(hidden)]
+            pub fn serialize<S, C, T, U>(items: &C, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+                for<'t> &'t C: IntoIterator<Item = &'t T>,
+                T: ::core::borrow::Borrow<[U]>,
+                U: ::bytemuck::NoUninit,
+            {
+                With::<$codec>::serialize(items, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D, C, T, U>(deserializer: D) -> Result<C, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                C: FromIterator<T>,
+                T: for<'t> TryFrom<&'t [U], Error: ::core::fmt::Display>,
+                U: ::bytemuck::AnyBitPattern + ::serde::Deserialize<'de>,
+            {
+                With::<$codec>::deserialize(deserializer)
+            }
+        }
+
+        #[doc = concat!("Writes the values of a map as `", $name, "` strings in human readable formats.")]
+        ///
+        /// See [`codec::bytes_if_readable::map_values`](crate::codec::bytes_if_readable::map_values).
+        pub mod map_values {
+            pub use $crate::codec::bytes_if_readable::map_values::With;
+
+            #[doc(hidden)]
+            pub fn serialize<S, M, K, T, U>(items: &M, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+                for<'t> &'t M: IntoIterator<Item = (&'t K, &'t T)>,
+                K: ::serde::Serialize,
+                T: ::core::borrow::Borrow<[U]>,
+                U: ::bytemuck::NoUninit,
+            {
+                With::<$codec>::serialize(items, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D, M, K, T, U>(deserializer: D) -> Result<M, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                M: FromIterator<(K, T)>,
+                K: ::serde::Deserialize<'de>,
+                T: for<'t> TryFrom<&'t [U], Error: ::core::fmt::Display>,
+                U: ::bytemuck::AnyBitPattern + ::serde::Deserialize<'de>,
+            {
+                With::<$codec>::deserialize(deserializer)
+            }
+        }
+
+        #[doc = concat!("Writes a `", $name, "` string in human readable formats and a sequence of `T` in binary formats.")]
+        ///
+        /// See [`codec::bytes_if_readable::legacy`](crate::codec::bytes_if_readable::legacy).
+        pub mod legacy {
+            pub use $crate::codec::bytes_if_readable::legacy::With;
+
+            #[doc(hidden)]
+            pub fn serialize<S, T, U>(item: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+                T: ::core::borrow::Borrow<[U]>,
+                U: ::bytemuck::NoUninit + ::serde::Serialize,
+            {
+                With::<$codec>::serialize(item, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D, T, U>(deserializer: D) -> Result<T, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                T: for<'t> TryFrom<&'t [U], Error: ::core::fmt::Display>,
+                U: ::bytemuck::AnyBitPattern + ::serde::Deserialize<'de>,
+            {
+                With::<$codec>::deserialize(deserializer)
+            }
+
+            #[doc = concat!("Writes a `", $name, "` string in human readable formats and a tuple of `T` in binary formats.")]
+            ///
+            /// See [`codec::bytes_if_readable::legacy::array`](crate::codec::bytes_if_readable::legacy::array).
+            pub mod array {
+                pub use $crate::codec::bytes_if_readable::legacy::array::With;
+
+                #[doc(hidden)]
+                pub fn serialize<S, U, const N: usize>(
+                    item: &[U; N],
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error>
+                where
+                    S: ::serde::Serializer,
+                    U: ::bytemuck::NoUninit + ::serde::Serialize,
+                {
+                    With::<$codec>::serialize(item, serializer)
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<'de, D, U, const N: usize>(
+                    deserializer: D,
+                ) -> Result<[U; N], D::Error>
+                where
+                    D: ::serde::Deserializer<'de>,
+                    U: ::bytemuck::AnyBitPattern + ::serde::Deserialize<'de>,
+                {
+                    With::<$codec>::deserialize(deserializer)
+                }
+            }
+        }
+    };
+}
+
+/// Generates the default `serialize` and `deserialize` functions and the combinators
+/// of a module with the representation of [`codec::string`], using the codec `$codec`.
+#[cfg(feature = "alloc")]
+macro_rules! string_module {
+    ($name: literal, $codec: ty) => {
+        pub use $crate::codec::string::With;
+
+        #[doc(hidden)]
+        pub fn serialize<S, T>(item: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ::serde::Serializer,
+            T: AsRef<str>,
+        {
+            With::<$codec>::serialize(item, serializer)
+        }
+
+        #[doc(hidden)]
+        pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+        where
+            D: ::serde::Deserializer<'de>,
+            T: TryFrom<::alloc::string::String, Error: ::core::fmt::Display>,
+        {
+            With::<$codec>::deserialize(deserializer)
+        }
+
+        #[doc = concat!("Writes `Option<T>` as a `", $name, "` string if the value is present.")]
+        ///
+        /// See [`codec::string::option`](crate::codec::string::option).
+        pub mod option {
+            pub use $crate::codec::string::option::With;
+
+            #[doc(hidden)]
+            pub fn serialize<S, T>(item: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+                T: AsRef<str>,
+            {
+                With::<$codec>::serialize(item, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                T: TryFrom<::alloc::string::String, Error: ::core::fmt::Display>,
+            {
+                With::<$codec>::deserialize(deserializer)
+            }
+        }
+
+        #[doc = concat!("Writes a collection of strings as a sequence of `", $name, "` strings.")]
+        ///
+        /// See [`codec::string::seq`](crate::codec::string::seq).
+        pub mod seq {
+            pub use $crate::codec::string::seq::With;
+
+            #[doc(hidden)]
+            pub fn serialize<S, C, T>(items: &C, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+                for<'t> &'t C: IntoIterator<Item = &'t T>,
+                T: AsRef<str>,
+            {
+                With::<$codec>::serialize(items, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D, C, T>(deserializer: D) -> Result<C, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                C: FromIterator<T>,
+                T: TryFrom<::alloc::string::String, Error: ::core::fmt::Display>,
+            {
+                With::<$codec>::deserialize(deserializer)
+            }
+        }
+
+        #[doc = concat!("Writes the string values of a map as `", $name, "` strings.")]
+        ///
+        /// See [`codec::string::map_values`](crate::codec::string::map_values).
+        pub mod map_values {
+            pub use $crate::codec::string::map_values::With;
+
+            #[doc(hidden)]
+            pub fn serialize<S, M, K, T>(items: &M, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+                for<'t> &'t M: IntoIterator<Item = (&'t K, &'t T)>,
+                K: ::serde::Serialize,
+                T: AsRef<str>,
+            {
+                With::<$codec>::serialize(items, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<'de, D, M, K, T>(deserializer: D) -> Result<M, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                M: FromIterator<(K, T)>,
+                K: ::serde::Deserialize<'de>,
+                T: TryFrom<::alloc::string::String, Error: ::core::fmt::Display>,
+            {
+                With::<$codec>::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+/// A `#[serde(with)]` module that "encrypts" a string as a `base64` string.
+///
+/// This supports types that implement [`AsRef<str>`] and [`TryFrom<String>`].
+///
+/// Use [`With`](base64_string::With) to choose a different [`Base64Engine`] or [`Codec`].
+///
+/// This requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub mod base64_string {
+    string_module!("base64", crate::engine::UrlSafe);
+}
+
+/// A `#[serde(with)]` adaptor that converts an array into a `base64` string.
+///
+/// This supports types that implement [`Borrow<[T]>`](core::borrow::Borrow) and [`TryFrom<&[T]>`](core::convert::TryFrom)
+/// and `T` implements [`bytemuck::AnyBitPattern`].
+///
+/// The output uses the byte order and pointer width of the machine,
+/// use [base64_le] or [base64_be] if the data is shared between machines.
+///
+/// Only encoded strings are accepted when deserializing. To migrate data stored as byte strings
+/// or sequences of `T` in self-describing formats like `json` or `cbor`, use [`lenient`][base64::lenient].
+///
+/// Use [`owned`][base64::owned] to move the decoded buffer into the target
+/// and [`array`](base64::array) to decode fixed size arrays without allocating.
+/// Decoding an array of the wrong length reports a [`DecodeError::Conversion`] here,
+/// while [`array`](base64::array) reports the expected length as [`DecodeError::WrongLength`].
+///
+/// Use [`option`][base64::option], [`seq`][base64::seq] and [`map_values`][base64::map_values]
+/// to encode the elements of an `Option`, a collection or the values of a map.
+/// Use [`map_keys`][base64::map_keys] to encode the keys of a map in human readable formats.
+///
+/// Use [`With`](base64::With) to choose a different [`Base64Engine`] or [`Codec`].
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "
+
+[base64_le]: crate#features
+[base64_be]: crate#features
+[base64::lenient]: crate#features
+[base64::owned]: crate#features
+[base64::option]: crate#features
+[base64::seq]: crate#features
+[base64::map_values]: crate#features
+[base64::map_keys]: crate#features
+[`DecodeError::Conversion`]: #features"
+)]
+pub mod base64 {
+    bytes_module!("base64", crate::engine::UrlSafe);
+}
+
+/// A `#[serde(with)]` adaptor that converts an array into a `base64` string only
+/// in human readable formats like `json` but not in binary formats like `postcard`.
+///
+/// This supports types that implement [`Borrow<[T]>`](core::borrow::Borrow) and [`TryFrom<&[T]>`](core::convert::TryFrom)
+/// and `T` implements [`bytemuck::AnyBitPattern`].
+///
+/// In binary formats the value is written as a byte string, using the byte order of the machine.
+/// Sequences of `T` are also accepted in self-describing formats.
+///
+/// Previous versions wrote a sequence of `T` in binary formats, and a tuple of `T` for fixed size arrays.
+/// Use [`legacy`](base64_if_readable::legacy) to read and write sequences in formats that are not self-describing
+/// like `postcard`, and [`legacy::array`](base64_if_readable::legacy::array) for arrays written as tuples.
+///
+/// Use [`With`](base64_if_readable::With) to choose a different [`Base64Engine`] or [`Codec`].
+///
+/// This requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub mod base64_if_readable {
+    bytes_if_readable_module!("base64", crate::engine::UrlSafe);
+}
+
+/// A `#[serde(with)]` adaptor that converts an array into a `hex` string.
+///
+/// This supports the same types as [base64], with the same combinators.
+///
+/// The output is lower case without a prefix, use [`With`](hex::With) with [`Upper`](hex::Upper),
+/// [`PrefixedLower`](hex::PrefixedLower) or [`PrefixedUpper`](hex::PrefixedUpper) to choose a different format.
+/// Both cases are accepted when deserializing.
+///
+/// ```
+/// # use serde::{Serialize, Deserialize};
+/// use serde_repr_base64::hex;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Account {
+///     #[serde(with = "hex::With::<hex::PrefixedLower>")]
+///     address: [u8; 20],
+/// }
+/// ```
+pub mod hex {
+    use core::fmt::Write;
+
+    use crate::{backend, Codec, DecodeError};
+
+    bytes_module!("hex", crate::hex::Lower);
+
+    /// Lower case digits, this is the default.
+    pub struct Lower;
+
+    /// Upper case digits.
+    pub struct Upper;
+
+    /// Lower case digits with a `0x` prefix.
+    ///
+    /// The prefix is optional when deserializing.
+    pub struct PrefixedLower;
+
+    /// Upper case digits with a `0x` prefix.
+    ///
+    /// The prefix is optional when deserializing.
+    pub struct PrefixedUpper;
+
+    const LOWER: &[u8; 16] = b"0123456789abcdef";
+    const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+    macro_rules! impl_codec {
+        ($ty: ty, $digits: expr, $prefix: literal) => {
+            impl Codec for $ty {
+                fn encoded_len(len: usize) -> Option<usize> {
+                    len.checked_mul(2)?.checked_add($prefix.len())
+                }
+
+                fn decoded_len_estimate(input: &[u8]) -> usize {
+                    input.len() / 2
+                }
+
+                fn decoded_len(input: &[u8]) -> Option<usize> {
+                    Some(strip_prefix(input, !$prefix.is_empty()).len() / 2)
+                }
+
+                fn encode<W: Write>(bytes: &[u8], out: &mut W) -> core::fmt::Result {
+                    out.write_str($prefix)?;
+                    encode(bytes, $digits, out)
+                }
+
+                fn decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+                    let digits = strip_prefix(input, !$prefix.is_empty());
+                    decode(digits, input.len() - digits.len(), output)
+                }
+            }
+        };
+    }
+
+    impl_codec!(Lower, LOWER, "");
+    impl_codec!(Upper, UPPER, "");
+    impl_codec!(PrefixedLower, LOWER, "0x");
+    impl_codec!(PrefixedUpper, UPPER, "0x");
+
+    fn strip_prefix(input: &[u8], prefixed: bool) -> &[u8] {
+        match input {
+            [b'0', b'x' | b'X', digits @ ..] if prefixed => digits,
+            _ => input,
+        }
+    }
+
+    fn encode<W: Write>(bytes: &[u8], digits: &[u8; 16], out: &mut W) -> core::fmt::Result {
+        const CHUNK: usize = 1024;
+        let mut buffer = [0; CHUNK * 2];
+        for chunk in bytes.chunks(CHUNK) {
+            for (byte, pair) in chunk.iter().zip(buffer.chunks_exact_mut(2)) {
+                pair[0] = digits[usize::from(byte >> 4)];
+                pair[1] = digits[usize::from(byte & 0xf)];
+            }
+            backend::write_ascii(&buffer[..chunk.len() * 2], out)?;
+        }
+        Ok(())
+    }
+
+    /// Decode `digits` into `output`, `offset` is the length of the prefix for error offsets.
+    fn decode(digits: &[u8], offset: usize, output: &mut [u8]) -> Result<usize, DecodeError> {
+        let len = digits.len() / 2;
+        if digits.len() % 2 != 0 || len > output.len() {
+            return Err(DecodeError::InvalidEncodedLength {
+                len: digits.len() + offset,
+            });
+        }
+        let value = |index: usize| {
+            let value = match digits[index] {
+                digit @ b'0'..=b'9' => digit - b'0',
+                digit @ b'a'..=b'f' => digit - b'a' + 10,
+                digit @ b'A'..=b'F' => digit - b'A' + 10,
+                _ => {
+                    return Err(DecodeError::InvalidSymbol {
+                        offset: index + offset,
+                    })
+                }
+            };
+            Ok(value)
+        };
+        for (index, byte) in output[..len].iter_mut().enumerate() {
+            *byte = value(index * 2)? << 4 | value(index * 2 + 1)?;
+        }
+        Ok(len)
+    }
+}
+
+/// A `#[serde(with)]` adaptor that converts an array into a `hex` string only
+/// in human readable formats like `json` but not in binary formats like `postcard`.
+///
+/// This has the same representation as [base64_if_readable].
+///
+/// Use [`With`](hex_if_readable::With) to choose a different format from [hex].
+///
+/// This requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub mod hex_if_readable {
+    bytes_if_readable_module!("hex", crate::hex::Lower);
+}
+
+/// A `#[serde(with)]` module that "encrypts" a string as a `hex` string.
+///
+/// This supports types that implement [`AsRef<str>`] and [`TryFrom<String>`].
+///
+/// Use [`With`](hex_string::With) to choose a different format from [hex].
+///
+/// This requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub mod hex_string {
+    string_module!("hex", crate::hex::Lower);
+}
+
+/// A `#[serde(with)]` adaptor that converts an array into a `base32` string.
+///
+/// This supports the same types as [base64], with the same combinators.
+///
+/// The output uses the [RFC 4648](https://datatracker.ietf.org/doc/html/rfc4648#section-6) alphabet with padding,
+/// use [`With`](base32::With) with [`Rfc4648NoPad`](base32::Rfc4648NoPad), [`Hex`](base32::Hex),
+/// [`HexNoPad`](base32::HexNoPad) or [`Crockford`](base32::Crockford) to choose a different alphabet.
+/// The output is upper case, both cases are accepted when deserializing.
+///
+/// ```
+/// # use serde::{Serialize, Deserialize};
+/// use serde_repr_base64::base32;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Order {
+///     #[serde(with = "base32::With::<base32::Crockford>")]
+///     code: [u8; 5],
+/// }
+/// ```
+pub mod base32 {
+    use core::fmt::Write;
+
+    use crate::{backend, Codec, DecodeError};
+
+    bytes_module!("base32", crate::base32::Rfc4648);
+
+    /// The RFC 4648 alphabet with padding, this is the default.
+    pub struct Rfc4648;
+
+    /// The RFC 4648 alphabet without padding.
+    pub struct Rfc4648NoPad;
+
+    /// The RFC 4648 "extended hex" alphabet with padding, which preserves the sort order.
+    pub struct Hex;
+
+    /// The RFC 4648 "extended hex" alphabet without padding.
+    pub struct HexNoPad;
+
+    /// [Crockford's](https://www.crockford.com/base32.html) alphabet without padding.
+    ///
+    /// When deserializing, `I` and `L` are read as `1`, `O` as `0` and hyphens are ignored.
+    pub struct Crockford;
+
+    /// The symbols and decoding rules of an alphabet.
+    struct Spec {
+        symbols: &'static [u8; 32],
+        /// The value of each byte, or [`INVALID`].
+        values: [u8; 256],
+        padding: bool,
+        hyphens: bool,
+    }
+
+    const INVALID: u8 = 0xff;
+
+    impl Spec {
+        const fn new(symbols: &'static [u8; 32], padding: bool) -> Self {
+            let mut values = [INVALID; 256];
+            let mut index = 0;
+            while index < symbols.len() {
+                values[symbols[index] as usize] = index as u8;
+                values[symbols[index].to_ascii_lowercase() as usize] = index as u8;
+                index += 1;
+            }
+            Spec {
+                symbols,
+                values,
+                padding,
+                hyphens: false,
+            }
+        }
+
+        const fn crockford() -> Self {
+            let mut spec = Spec::new(b"0123456789ABCDEFGHJKMNPQRSTVWXYZ", false);
+            spec.values[b'I' as usize] = 1;
+            spec.values[b'i' as usize] = 1;
+            spec.values[b'L' as usize] = 1;
+            spec.values[b'l' as usize] = 1;
+            spec.values[b'O' as usize] = 0;
+            spec.values[b'o' as usize] = 0;
+            spec.hyphens = true;
+            spec
+        }
+    }
+
+    const RFC4648: Spec = Spec::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567", true);
+    const RFC4648_NO_PAD: Spec = Spec::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567", false);
+    const HEX: Spec = Spec::new(b"0123456789ABCDEFGHIJKLMNOPQRSTUV", true);
+    const HEX_NO_PAD: Spec = Spec::new(b"0123456789ABCDEFGHIJKLMNOPQRSTUV", false);
+    const CROCKFORD: Spec = Spec::crockford();
+
+    macro_rules! impl_codec {
+        ($ty: ty, $spec: expr) => {
+            impl Codec for $ty {
+                fn encoded_len(len: usize) -> Option<usize> {
+                    Some(encoded_len(len, $spec.padding))
+                }
+
+                fn decoded_len_estimate(input: &[u8]) -> usize {
+                    input.len() / 8 * 5 + input.len() % 8 * 5 / 8
+                }
+
+                fn decoded_len(input: &[u8]) -> Option<usize> {
+                    let symbols = input
+                        .iter()
+                        .filter(|x| **x != b'=' && !($spec.hyphens && **x == b'-'))
+                        .count();
+                    Some(symbols / 8 * 5 + symbols % 8 * 5 / 8)
+                }
+
+                fn encode<W: Write>(bytes: &[u8], out: &mut W) -> core::fmt::Result {
+                    encode(bytes, &$spec, out)
+                }
+
+                fn decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+                    decode(input, &$spec, output)
+                }
+            }
+        };
+    }
+
+    impl_codec!(Rfc4648, RFC4648);
+    impl_codec!(Rfc4648NoPad, RFC4648_NO_PAD);
+    impl_codec!(Hex, HEX);
+    impl_codec!(HexNoPad, HEX_NO_PAD);
+    impl_codec!(Crockford, CROCKFORD);
+
+    fn encoded_len(len: usize, padding: bool) -> usize {
+        let symbols = len % 5 * 8;
+        let partial = if padding {
+            symbols.div_ceil(40) * 8
+        } else {
+            symbols.div_ceil(5)
+        };
+        (len / 5).saturating_mul(8).saturating_add(partial)
+    }
+
+    fn encode<W: Write>(bytes: &[u8], spec: &Spec, out: &mut W) -> core::fmt::Result {
+        // A multiple of 5 so only the last chunk can be padded.
+        const CHUNK: usize = 5 * 128;
+        let mut buffer = [0; CHUNK / 5 * 8];
+        for chunk in bytes.chunks(CHUNK) {
+            let mut len = 0;
+            for group in chunk.chunks(5) {
+                let mut block = [0; 8];
+                block[3..3 + group.len()].copy_from_slice(group);
+                let bits = u64::from_be_bytes(block);
+                let symbols = (group.len() * 8).div_ceil(5);
+                for (index, symbol) in buffer[len..len + 8].iter_mut().enumerate() {
+                    *symbol = if index < symbols {
+                        spec.symbols[(bits >> (35 - index * 5)) as usize & 31]
+                    } else {
+                        b'='
+                    };
+                }
+                len += if spec.padding { 8 } else { symbols };
+            }
+            backend::write_ascii(&buffer[..len], out)?;
+        }
+        Ok(())
+    }
+
+    fn decode(input: &[u8], spec: &Spec, output: &mut [u8]) -> Result<usize, DecodeError> {
+        let end = input.iter().rposition(|x| *x != b'=').map_or(0, |x| x + 1);
+        let symbols = input[..end]
+            .iter()
+            .filter(|x| !(spec.hyphens && **x == b'-'))
+            .count();
+        let expected_padding = match symbols % 8 {
+            0 => 0,
+            2 => 6,
+            4 => 4,
+            5 => 3,
+            7 => 1,
+            _ => return Err(DecodeError::InvalidEncodedLength { len: input.len() }),
+        };
+        let padding = input.len() - end;
+        if padding != if spec.padding { expected_padding } else { 0 } {
+            return Err(DecodeError::InvalidPadding);
+        }
+        let len = symbols / 8 * 5 + symbols % 8 * 5 / 8;
+        if len > output.len() {
+            return Err(DecodeError::InvalidEncodedLength { len: input.len() });
+        }
+
+        let (mut bits, mut count, mut written, mut last) = (0u32, 0, 0, 0);
+        for (offset, symbol) in input[..end].iter().enumerate() {
+            if spec.hyphens && *symbol == b'-' {
+                continue;
+            }
+            let value = spec.values[usize::from(*symbol)];
+            if value == INVALID {
+                return Err(DecodeError::InvalidSymbol { offset });
+            }
+            bits = bits << 5 | u32::from(value);
+            count += 5;
+            if count >= 8 {
+                count -= 8;
+                output[written] = (bits >> count) as u8;
+                written += 1;
+            }
+            last = offset;
+        }
+        // The unused bits of the final symbol must be zero, so every value has one encoding.
+        if bits & ((1 << count) - 1) != 0 {
+            return Err(DecodeError::InvalidSymbol { offset: last });
+        }
+        Ok(written)
+    }
+}
+
+/// A `#[serde(with)]` adaptor that converts an array into a `base32` string only
+/// in human readable formats like `json` but not in binary formats like `postcard`.
+///
+/// This has the same representation as [base64_if_readable].
+///
+/// Use [`With`](base32_if_readable::With) to choose a different alphabet from [base32].
+///
+/// This requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub mod base32_if_readable {
+    bytes_if_readable_module!("base32", crate::base32::Rfc4648);
+}
+
+/// A `#[serde(with)]` module that "encrypts" a string as a `base32` string.
+///
+/// This supports types that implement [`AsRef<str>`] and [`TryFrom<String>`].
+///
+/// Use [`With`](base32_string::With) to choose a different alphabet from [base32].
+///
+/// This requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub mod base32_string {
+    string_module!("base32", crate::base32::Rfc4648);
+}
+
+/// A `#[serde(with)]` adaptor that converts an array into a `base58` string.
+///
+/// This supports the same types as [base64], with the same combinators.
+///
+/// The output uses the [`Bitcoin`](base58::Bitcoin) alphabet,
+/// use [`With`](base58::With) with [`Ripple`](base58::Ripple) or [`Flickr`](base58::Flickr)
+/// to choose a different alphabet, and [`Check`](base58::Check) to append a checksum.
+///
+/// ```
+/// # use serde::{Serialize, Deserialize};
+/// use serde_repr_base64::base58;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Address {
+///     #[serde(with = "base58::With::<base58::Check>")]
+///     payload: [u8; 21],
+/// }
+/// ```
+///
+/// This requires the `base58` feature.
+#[cfg(feature = "base58")]
+pub mod base58 {
+    use alloc::vec::Vec;
+    use core::{fmt::Write, marker::PhantomData};
+
+    use crate::{Codec, DecodeError};
+
+    bytes_module!("base58", crate::base58::Bitcoin);
+
+    /// An alphabet usable with [`Check`].
+    ///
+    /// This is implemented by [`Bitcoin`], [`Ripple`] and [`Flickr`].
+    pub trait Alphabet {
+        const ALPHABET: &'static bs58::Alphabet;
+    }
+
+    /// The Bitcoin and IPFS alphabet, this is the default.
+    pub struct Bitcoin;
+
+    /// The Ripple alphabet.
+    pub struct Ripple;
+
+    /// The Flickr alphabet.
+    pub struct Flickr;
+
+    /// Base58Check, which appends the first 4 bytes of the double SHA-256 of the data
+    /// before encoding and verifies them when deserializing.
+    pub struct Check<A = Bitcoin>(PhantomData<A>);
+
+    impl Alphabet for Bitcoin {
+        const ALPHABET: &'static bs58::Alphabet = bs58::Alphabet::BITCOIN;
+    }
+
+    impl Alphabet for Ripple {
+        const ALPHABET: &'static bs58::Alphabet = bs58::Alphabet::RIPPLE;
+    }
+
+    impl Alphabet for Flickr {
+        const ALPHABET: &'static bs58::Alphabet = bs58::Alphabet::FLICKR;
+    }
+
+    /// Length of the Base58Check checksum.
+    const CHECKSUM: usize = 4;
+
+    /// An upper bound of the encoded length of `len` bytes, `log(256) / log(58)` is about 1.366.
+    fn encoded_len(len: usize) -> usize {
+        len.saturating_mul(1366).div_ceil(1000).saturating_add(1)
+    }
+
+    macro_rules! impl_codec {
+        ($($ty: ty),*) => {$(
+            impl Codec for $ty {
+                fn encoded_len(len: usize) -> Option<usize> {
+                    Some(encoded_len(len))
+                }
+
+                fn decoded_len_estimate(input: &[u8]) -> usize {
+                    input.len()
+                }
+
+                fn decoded_len(input: &[u8]) -> Option<usize> {
+                    let decoded = bs58::decode(input).with_alphabet(Self::ALPHABET).into_vec();
+                    decoded.ok().map(|decoded| decoded.len())
+                }
+
+                fn encode<W: Write>(bytes: &[u8], out: &mut W) -> core::fmt::Result {
+                    let encoded = bs58::encode(bytes).with_alphabet(Self::ALPHABET).into_string();
+                    out.write_str(&encoded)
+                }
+
+                fn decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+                    let decoded = bs58::decode(input).with_alphabet(Self::ALPHABET).into_vec();
+                    copy(decoded, input.len(), output)
+                }
+            }
+        )*};
+    }
+
+    impl_codec!(Bitcoin, Ripple, Flickr);
+
+    impl<A: Alphabet> Codec for Check<A> {
+        fn encoded_len(len: usize) -> Option<usize> {
+            Some(encoded_len(len.saturating_add(CHECKSUM)))
+        }
+
+        fn decoded_len_estimate(input: &[u8]) -> usize {
+            input.len().saturating_sub(CHECKSUM)
+        }
+
+        fn decoded_len(input: &[u8]) -> Option<usize> {
+            let decoded = bs58::decode(input)
+                .with_alphabet(A::ALPHABET)
+                .with_check(None)
+                .into_vec();
+            decoded.ok().map(|decoded| decoded.len())
+        }
+
+        fn encode<W: Write>(bytes: &[u8], out: &mut W) -> core::fmt::Result {
+            let encoded = bs58::encode(bytes)
+                .with_alphabet(A::ALPHABET)
+                .with_check()
+                .into_string();
+            out.write_str(&encoded)
+        }
+
+        fn decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+            let decoded = bs58::decode(input)
+                .with_alphabet(A::ALPHABET)
+                .with_check(None)
+                .into_vec();
+            copy(decoded, input.len(), output)
+        }
+    }
+
+    /// Copy the result of decoding `len` symbols into `output`.
+    ///
+    /// The decoded length depends on the value, so `bs58` decodes into a scratch buffer
+    /// and reports invalid symbols and checksums before a wrong length.
+    fn copy(
+        decoded: Result<Vec<u8>, bs58::decode::Error>,
+        len: usize,
+        output: &mut [u8],
+    ) -> Result<usize, DecodeError> {
+        let decoded = decoded.map_err(|e| error(e, len))?;
+        output
+            .get_mut(..decoded.len())
+            .ok_or(DecodeError::InvalidEncodedLength { len })?
+            .copy_from_slice(&decoded);
+        Ok(decoded.len())
+    }
+
+    fn error(error: bs58::decode::Error, len: usize) -> DecodeError {
+        match error {
+            bs58::decode::Error::InvalidCharacter { index, .. }
+            | bs58::decode::Error::NonAsciiCharacter { index } => {
+                DecodeError::InvalidSymbol { offset: index }
+            }
+            bs58::decode::Error::InvalidChecksum { .. }
+            | bs58::decode::Error::InvalidVersion { .. }
+            | bs58::decode::Error::NoChecksum => DecodeError::InvalidChecksum,
+            _ => DecodeError::InvalidEncodedLength { len },
+        }
+    }
+}
+
+/// A `#[serde(with)]` adaptor that converts an array into a `base58` string only
+/// in human readable formats like `json` but not in binary formats like `postcard`.
+///
+/// This has the same representation as [base64_if_readable],
+/// the checksum of [`Check`](base58::Check) is only written in human readable formats.
+///
+/// Use [`With`](base58_if_readable::With) to choose a different alphabet from [base58].
+///
+/// This requires the `base58` feature.
+#[cfg(feature = "base58")]
+pub mod base58_if_readable {
+    bytes_if_readable_module!("base58", crate::base58::Bitcoin);
+}
+
+/// A `#[serde(with)]` adaptor that converts an array into a `z85` string.
+///
+/// This supports the same types as [base64], with the same combinators.
+///
+/// [Z85](https://rfc.zeromq.org/spec/32/) encodes every 4 bytes as 5 symbols that are safe
+/// to use in `json` strings. Z85 requires a multiple of 4 bytes, other lengths are padded
+/// like [ascii85]: the last `n` bytes are written as `n + 1` symbols.
+/// Deserializing fails with [`DecodeError::InvalidEncodedLength`] if the input is not
+/// a multiple of 5 symbols plus such a tail.
+///
+/// ```
+/// # use serde::{Serialize, Deserialize};
+/// use serde_repr_base64::z85;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Embedding {
+///     #[serde(with = "z85")]
+///     vector: Vec<f32>,
+/// }
+/// ```
+pub mod z85 {
+    use core::fmt::Write;
+
+    use crate::base85::{self, Spec};
+    use crate::{Codec, DecodeError};
+
+    bytes_module!("z85", crate::z85::Z85);
+
+    /// The Z85 alphabet, padded for lengths that are not a multiple of 4.
+    pub struct Z85;
+
+    const SPEC: Spec = Spec::new(
+        b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#",
+        None,
+    );
+
+    impl Codec for Z85 {
+        fn encoded_len(len: usize) -> Option<usize> {
+            Some(base85::encoded_len(len))
+        }
+
+        fn decoded_len_estimate(input: &[u8]) -> usize {
+            base85::decoded_len(input.len())
+        }
+
+        fn decoded_len(input: &[u8]) -> Option<usize> {
+            Some(base85::decoded_len(input.len()))
+        }
+
+        fn encode<W: Write>(bytes: &[u8], out: &mut W) -> core::fmt::Result {
+            base85::encode(bytes, &SPEC, out)
+        }
+
+        fn decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+            base85::decode(input, &SPEC, output)
+        }
+    }
+}
+
+/// A `#[serde(with)]` adaptor that converts an array into an `ascii85` string.
+///
+/// This supports the same types as [base64], with the same combinators.
+///
+/// Every 4 bytes are written as 5 symbols from `!` to `u`, or `z` if all 4 bytes are zero.
+/// The last `n` bytes are written as `n + 1` symbols if the length is not a multiple of 4.
+/// The output does not include the `<~` and `~>` delimiters of Adobe's variant.
+pub mod ascii85 {
+    use core::fmt::Write;
+
+    use crate::base85::{self, Spec};
+    use crate::{Codec, DecodeError};
+
+    bytes_module!("ascii85", crate::ascii85::Ascii85);
+
+    /// The Ascii85 alphabet, with `z` for 4 zero bytes.
+    pub struct Ascii85;
+
+    const SPEC: Spec = Spec::new(
+        b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu",
+        Some(b'z'),
+    );
+
+    impl Codec for Ascii85 {
+        fn encoded_len(len: usize) -> Option<usize> {
+            Some(base85::encoded_len(len))
+        }
+
+        fn decoded_len_estimate(input: &[u8]) -> usize {
+            input.len().saturating_mul(4)
+        }
+
+        fn decoded_len(input: &[u8]) -> Option<usize> {
+            let zeros = input.iter().filter(|x| **x == b'z').count();
+            Some(zeros * 4 + base85::decoded_len(input.len() - zeros))
+        }
+
+        fn encode<W: Write>(bytes: &[u8], out: &mut W) -> core::fmt::Result {
+            base85::encode(bytes, &SPEC, out)
+        }
+
+        fn decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+            base85::decode(input, &SPEC, output)
+        }
+    }
+}
+
+/// `#[serde(with)]` adaptors that convert an array into a `bech32` string, like `npub1...`.
+///
+/// This supports the same types as [base64], with the same combinators.
+///
+/// There is no default human readable part, so the codec always has to be chosen with `With`,
+/// using [`Bech32`](bech32::Bech32) or [`Bech32m`](bech32::Bech32m) and an [`Hrp`](bech32::Hrp).
+/// The human readable part and the checksum are verified when deserializing.
+/// Serializing fails with [`EncodeError::TooLong`] when the string would exceed 1023 symbols,
+/// and with [`EncodeError::InvalidPrefix`] when the human readable part is not valid.
+///
+/// ```
+/// # use serde::{Serialize, Deserialize};
+/// use serde_repr_base64::bech32::{self, Bech32};
+///
+/// pub struct Npub;
+///
+/// impl bech32::Hrp for Npub {
+///     const HRP: &'static str = "npub";
+/// }
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Profile {
+///     #[serde(with = "bech32::With::<Bech32<Npub>>")]
+///     public_key: [u8; 32],
+/// }
+/// ```
+///
+/// This requires the `bech32` feature.
+#[cfg(feature = "bech32")]
+pub mod bech32 {
+    use core::{fmt::Write, marker::PhantomData};
+
+    use ::bech32::{primitives::decode::CheckedHrpstring, Checksum, Fe32};
+
+    pub use crate::codec::bytes::{array, With};
+    #[cfg(feature = "alloc")]
+    pub use crate::codec::bytes::{lenient, map_keys, map_values, option, owned, seq};
+    use crate::{Codec, DecodeError, EncodeError};
+
+    /// The human readable part of a [`Bech32`] or [`Bech32m`] string.
+    pub trait Hrp {
+        /// The human readable part, `npub` for `npub1...`.
+        const HRP: &'static str;
+    }
+
+    /// Bech32 as specified by [BIP-173](https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki).
+    pub struct Bech32<H>(PhantomData<H>);
+
+    /// Bech32m as specified by [BIP-350](https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki),
+    /// which fixes a weakness of the [`Bech32`] checksum.
+    pub struct Bech32m<H>(PhantomData<H>);
+
+    /// Length of the checksum in symbols.
+    const CHECKSUM: usize = 6;
+
+    macro_rules! impl_codec {
+        ($ty: ident) => {
+            impl<H: Hrp> Codec for $ty<H> {
+                fn encoded_len(len: usize) -> Option<usize> {
+                    (H::HRP.len() + 1 + CHECKSUM).checked_add(len.checked_mul(8)?.div_ceil(5))
+                }
+
+                fn decoded_len_estimate(input: &[u8]) -> usize {
+                    input.len() * 5 / 8
+                }
+
+                fn decoded_len(input: &[u8]) -> Option<usize> {
+                    decoded_len(H::HRP, input)
+                }
+
+                fn validate(bytes: &[u8]) -> Result<(), EncodeError> {
+                    validate::<::bech32::$ty>(H::HRP, bytes)
+                }
+
+                fn encode<W: Write>(bytes: &[u8], out: &mut W) -> core::fmt::Result {
+                    let hrp = ::bech32::Hrp::parse(H::HRP).map_err(|_| core::fmt::Error)?;
+                    ::bech32::encode_lower_to_fmt::<::bech32::$ty, W>(out, hrp, bytes)
+                        .map_err(|_| core::fmt::Error)
+                }
+
+                fn decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+                    decode::<::bech32::$ty>(H::HRP, input, output)
+                }
+            }
+        };
+    }
+
+    impl_codec!(Bech32);
+    impl_codec!(Bech32m);
+
+    /// The decoded length of `input`, or `None` if its human readable part is not `hrp`
+    /// so that decoding reports the actual error.
+    fn decoded_len(hrp: &str, input: &[u8]) -> Option<usize> {
+        let separator = input.iter().rposition(|x| *x == b'1')?;
+        if !input[..separator].eq_ignore_ascii_case(hrp.as_bytes()) {
+            return None;
+        }
+        Some((input.len() - separator - 1).checked_sub(CHECKSUM)? * 5 / 8)
+    }
+
+    fn validate<Ck: Checksum>(hrp: &str, bytes: &[u8]) -> Result<(), EncodeError> {
+        let hrp = ::bech32::Hrp::parse(hrp).map_err(|_| EncodeError::InvalidPrefix)?;
+        ::bech32::encoded_length::<Ck>(hrp, bytes).map_err(|e| EncodeError::TooLong {
+            len: e.encoded_length,
+            max: e.code_length,
+        })?;
+        Ok(())
+    }
+
+    fn decode<Ck: Checksum>(
+        hrp: &str,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<usize, DecodeError> {
+        let string = core::str::from_utf8(input).map_err(|e| DecodeError::InvalidSymbol {
+            offset: e.valid_up_to(),
+        })?;
+        let checked = CheckedHrpstring::new::<Ck>(string).map_err(|e| error(e, input))?;
+        if ::bech32::Hrp::parse(hrp).ok() != Some(checked.hrp()) {
+            return Err(DecodeError::InvalidPrefix);
+        }
+        let data = checked.data_part_ascii_no_checksum();
+        let len = data.len() * 5 / 8;
+        let padding = data.len() * 5 % 8;
+        if padding >= 5 || len > output.len() {
+            return Err(DecodeError::InvalidEncodedLength { len: input.len() });
+        }
+        // The padding bits of the final symbol must be zero, so every value has one encoding.
+        if let Some(last) = data.last() {
+            let value = Fe32::from_char(char::from(*last)).map_or(0, |x| x.to_u8());
+            if value & ((1 << padding) - 1) != 0 {
+                return Err(DecodeError::InvalidSymbol {
+                    offset: input.len() - CHECKSUM - 1,
+                });
+            }
+        }
+        for (byte, decoded) in output.iter_mut().zip(checked.byte_iter()) {
+            *byte = decoded;
+        }
+        Ok(len)
+    }
+
+    fn error(
+        error: ::bech32::primitives::decode::CheckedHrpstringError,
+        input: &[u8],
+    ) -> DecodeError {
+        use ::bech32::primitives::decode::{
+            CharError, CheckedHrpstringError, ChecksumError, UncheckedHrpstringError,
+        };
+
+        match error {
+            CheckedHrpstringError::Parse(UncheckedHrpstringError::Char(
+                CharError::InvalidChar(c),
+            )) => {
+                let offset = input.iter().position(|x| char::from(*x) == c);
+                DecodeError::InvalidSymbol {
+                    offset: offset.unwrap_or_default(),
+                }
+            }
+            CheckedHrpstringError::Parse(UncheckedHrpstringError::Char(CharError::MixedCase)) => {
+                let offset = input.iter().position(u8::is_ascii_uppercase);
+                DecodeError::InvalidSymbol {
+                    offset: offset.unwrap_or_default(),
+                }
+            }
+            CheckedHrpstringError::Parse(UncheckedHrpstringError::Char(
+                CharError::MissingSeparator,
+            ))
+            | CheckedHrpstringError::Parse(UncheckedHrpstringError::Hrp(_)) => {
+                DecodeError::InvalidPrefix
+            }
+            CheckedHrpstringError::Checksum(ChecksumError::InvalidResidue) => {
+                DecodeError::InvalidChecksum
+            }
+            _ => DecodeError::InvalidEncodedLength { len: input.len() },
+        }
+    }
+}
+
+/// A `#[serde(with)]` adaptor that converts an array into a `base45` string.
+///
+/// This supports the same types as [base64], with the same combinators.
+///
+/// [Base45](https://datatracker.ietf.org/doc/html/rfc9285) writes every 2 bytes as 3 symbols
+/// that fit the alphanumeric mode of QR codes.
+pub mod base45 {
+    use core::fmt::Write;
+
+    use crate::{backend, Codec, DecodeError};
+
+    bytes_module!("base45", crate::base45::Base45);
+
+    /// The RFC 9285 alphabet.
+    pub struct Base45;
+
+    const SYMBOLS: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+    impl Codec for Base45 {
+        fn encoded_len(len: usize) -> Option<usize> {
+            (len / 2).checked_mul(3)?.checked_add(len % 2 * 2)
+        }
+
+        fn decoded_len_estimate(input: &[u8]) -> usize {
+            input.len() / 3 * 2 + input.len() % 3 / 2
+        }
+
+        fn decoded_len(input: &[u8]) -> Option<usize> {
+            Some(Self::decoded_len_estimate(input))
+        }
+
+        fn encode<W: Write>(bytes: &[u8], out: &mut W) -> core::fmt::Result {
+            const CHUNK: usize = 2 * 512;
+            let mut buffer = [0; CHUNK / 2 * 3];
+            for chunk in bytes.chunks(CHUNK) {
+                let mut len = 0;
+                for group in chunk.chunks(2) {
+                    let mut value = group
+                        .iter()
+                        .fold(0, |value, x| value * 256 + usize::from(*x));
+                    // The least significant symbol comes first.
+                    for symbol in &mut buffer[len..len + group.len() + 1] {
+                        *symbol = SYMBOLS[value % 45];
+                        value /= 45;
+                    }
+                    len += group.len() + 1;
+                }
+                backend::write_ascii(&buffer[..len], out)?;
+            }
+            Ok(())
+        }
+
+        fn decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+            let len = Self::decoded_len_estimate(input);
+            if input.len() % 3 == 1 || len > output.len() {
+                return Err(DecodeError::InvalidEncodedLength { len: input.len() });
+            }
+            for (index, (group, bytes)) in input.chunks(3).zip(output.chunks_mut(2)).enumerate() {
+                let offset = index * 3;
+                let mut value = 0;
+                for (position, symbol) in group.iter().enumerate().rev() {
+                    let digit = SYMBOLS.iter().position(|x| x == symbol).ok_or(
+                        DecodeError::InvalidSymbol {
+                            offset: offset + position,
+                        },
+                    )?;
+                    value = value * 45 + digit;
+                }
+                // 3 symbols can exceed 2 bytes and 2 symbols can exceed 1 byte.
+                let max = if group.len() == 3 { 0xffff } else { 0xff };
+                if value > max {
+                    return Err(DecodeError::InvalidSymbol { offset });
+                }
+                let value = (value as u16).to_be_bytes();
+                let value = &value[2 - (group.len() - 1)..];
+                bytes[..value.len()].copy_from_slice(value);
+            }
+            Ok(len)
+        }
+    }
+}
+
+/// A `#[serde(with)]` adaptor that converts an array into a self describing
+/// [multibase](https://github.com/multiformats/multibase) string.
+///
+/// This supports the same types as [base64], with the same combinators.
+///
+/// The output is prefixed by the multibase code of the encoding, `u` for `base64url` by default.
+/// Use [`With`](multibase::With) with [`Multibase`](multibase::Multibase) to choose a different encoding,
+/// any supported encoding is accepted when deserializing regardless of this choice,
+/// so the encoding of a field can change without breaking readers.
+///
+/// | Code | Encoding |
+/// | ---- | -------- |
+/// | `f`, `F` | [`hex::Lower`], [`hex::Upper`] |
+/// | `B`, `C` | [`base32::Rfc4648NoPad`], [`base32::Rfc4648`] |
+/// | `V`, `T` | [`base32::HexNoPad`], [`base32::Hex`] |
+/// | `z`, `Z` | `base58::Bitcoin`, `base58::Flickr`, with the `base58` feature |
+/// | `m`, `M` | [`engine::StandardNoPad`], [`engine::Standard`] |
+/// | `u`, `U` | [`engine::UrlSafeNoPad`], [`engine::UrlSafe`] |
+/// | `R` | [`base45::Base45`] |
+///
+/// The lower case `base32` codes `b`, `c`, `v` and `t` are also accepted when deserializing.
+///
+/// ```
+/// # use serde::{Serialize, Deserialize};
+/// use serde_repr_base64::{multibase::{self, Multibase}, hex};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Content {
+///     #[serde(with = "multibase::With::<Multibase<hex::Lower>>")]
+///     digest: [u8; 4],
+/// }
+///
+/// let json = serde_json::to_string(&Content { digest: [1, 2, 3, 4] }).unwrap();
+/// assert_eq!(json, r#"{"digest":"f01020304"}"#);
+/// let content: Content = serde_json::from_str(r#"{"digest":"uAQIDBA"}"#).unwrap();
+/// assert_eq!(content.digest, [1, 2, 3, 4]);
+/// ```
+pub mod multibase {
+    use core::{fmt::Write, marker::PhantomData};
+
+    use crate::engine::{Standard, StandardNoPad, UrlSafe, UrlSafeNoPad};
+    use crate::{base32, base45, hex};
+    use crate::{Codec, DecodeError};
+
+    bytes_module!("multibase", crate::multibase::Multibase);
+
+    /// A [`Codec`] with a multibase code, usable with [`Multibase`].
+    ///
+    /// Only the encodings in the table of [multibase](crate::multibase) are accepted when deserializing.
+    pub trait Base: Codec {
+        /// The multibase code, an ascii character.
+        const CODE: u8;
+    }
+
+    /// Writes the multibase code of `B` before the encoded string, `base64url` by default.
+    pub struct Multibase<B = UrlSafeNoPad>(PhantomData<B>);
+
+    impl<B: Base> Codec for Multibase<B> {
+        fn encoded_len(len: usize) -> Option<usize> {
+            B::encoded_len(len)?.checked_add(1)
+        }
+
+        fn decoded_len_estimate(input: &[u8]) -> usize {
+            match input {
+                [code, input @ ..] => decoded_len_estimate(*code, input),
+                [] => 0,
+            }
+        }
+
+        fn decoded_len(input: &[u8]) -> Option<usize> {
+            match input {
+                [code, input @ ..] => decoded_len(*code, input),
+                [] => None,
+            }
+        }
+
+        fn encode<W: Write>(bytes: &[u8], out: &mut W) -> core::fmt::Result {
+            out.write_char(char::from(B::CODE))?;
+            B::encode(bytes, out)
+        }
+
+        fn decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+            let [code, input @ ..] = input else {
+                return Err(DecodeError::InvalidPrefix);
+            };
+            // Offsets and lengths include the code.
+            decode(*code, input, output).map_err(|e| match e {
+                DecodeError::InvalidSymbol { offset } => {
+                    DecodeError::InvalidSymbol { offset: offset + 1 }
+                }
+                DecodeError::InvalidEncodedLength { len } => {
+                    DecodeError::InvalidEncodedLength { len: len + 1 }
+                }
+                e => e,
+            })
+        }
+    }
+
+    /// Implements [`Base`] for the written encodings,
+    /// and dispatches to all accepted encodings by their code when deserializing.
+    macro_rules! bases {
+        (
+            written: { $($(#[$written_cfg: meta])* $code: literal => $ty: ty,)* }
+            accepted: { $($(#[$accepted_cfg: meta])* $alias: literal => $alias_ty: ty,)* }
+        ) => {
+            $(
+                $(#[$written_cfg])*
+                impl Base for $ty {
+                    const CODE: u8 = $code;
+                }
+            )*
+
+            fn decoded_len_estimate(code: u8, input: &[u8]) -> usize {
+                match code {
+                    $($(#[$written_cfg])* $code => <$ty>::decoded_len_estimate(input),)*
+                    $($(#[$accepted_cfg])* $alias => <$alias_ty>::decoded_len_estimate(input),)*
+                    _ => 0,
+                }
+            }
+
+            fn decoded_len(code: u8, input: &[u8]) -> Option<usize> {
+                match code {
+                    $($(#[$written_cfg])* $code => <$ty>::decoded_len(input),)*
+                    $($(#[$accepted_cfg])* $alias => <$alias_ty>::decoded_len(input),)*
+                    _ => None,
+                }
+            }
+
+            fn decode(code: u8, input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+                match code {
+                    $($(#[$written_cfg])* $code => <$ty>::decode(input, output),)*
+                    $($(#[$accepted_cfg])* $alias => <$alias_ty>::decode(input, output),)*
+                    _ => Err(DecodeError::InvalidPrefix),
+                }
+            }
+        };
+    }
 
-/// A `#[serde(with)]` module that "encrypts" a string as a `base64` string.
-///
-/// This supports types that implement [`AsRef<str>`] and [`TryFrom<String>`].
-pub mod base64_string {
-    use std::fmt::Display;
+    bases! {
+        written: {
+            b'f' => hex::Lower,
+            b'F' => hex::Upper,
+            b'B' => base32::Rfc4648NoPad,
+            b'C' => base32::Rfc4648,
+            b'V' => base32::HexNoPad,
+            b'T' => base32::Hex,
+            #[cfg(feature = "base58")]
+            b'z' => crate::base58::Bitcoin,
+            #[cfg(feature = "base58")]
+            b'Z' => crate::base58::Flickr,
+            b'm' => StandardNoPad,
+            b'M' => Standard,
+            b'u' => UrlSafeNoPad,
+            b'U' => UrlSafe,
+            b'R' => base45::Base45,
+        }
+        accepted: {
+            b'b' => base32::Rfc4648NoPad,
+            b'c' => base32::Rfc4648,
+            b'v' => base32::HexNoPad,
+            b't' => base32::Hex,
+        }
+    }
+}
 
-    use base64::{engine::general_purpose::URL_SAFE, Engine};
-    use serde::{Deserialize, Deserializer, Serializer};
+mod wrapper {
+    use core::{
+        borrow::Borrow,
+        fmt::{Debug, Display},
+        marker::PhantomData,
+    };
 
-    #[doc(hidden)]
-    pub fn serialize<S: Serializer, T: AsRef<str>>(
-        item: &T,
-        serializer: S,
-    ) -> Result<S::Ok, S::Error> {
-        serializer.serialize_str(&URL_SAFE.encode(item.as_ref().as_bytes()))
+    use bytemuck::NoUninit;
+    use serde::{Serialize, Serializer};
+
+    use crate::backend::Encoded;
+    use crate::codec::Codec;
+    use crate::engine::UrlSafe;
+
+    #[cfg(feature = "alloc")]
+    use alloc::string::String;
+    #[cfg(feature = "alloc")]
+    use bytemuck::AnyBitPattern;
+    #[cfg(feature = "alloc")]
+    use core::str::FromStr;
+    #[cfg(feature = "alloc")]
+    use serde::{Deserialize, Deserializer};
+
+    #[cfg(feature = "alloc")]
+    use crate::typed::decode_typed;
+    #[cfg(feature = "alloc")]
+    use crate::DecodeError;
+
+    /// Write `bytes` encoded by `E`, this fails if [`Codec::validate`] rejects them.
+    fn display<E: Codec>(bytes: &[u8], f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        E::validate(bytes).map_err(|_| core::fmt::Error)?;
+        Display::fmt(&Encoded::<E>::new(bytes), f)
     }
 
-    #[doc(hidden)]
-    pub fn deserialize<'de, D: Deserializer<'de>, T: TryFrom<String, Error: Display>>(
-        deserializer: D,
-    ) -> Result<T, D::Error> {
-        T::try_from(
-            String::from_utf8(
-                URL_SAFE
-                    .decode(String::deserialize(deserializer)?)
-                    .map_err(serde::de::Error::custom)?,
-            )
-            .map_err(serde::de::Error::custom)?,
-        )
-        .map_err(serde::de::Error::custom)
+    /// Write `bytes` encoded by `E` in a tuple named `name`,
+    /// or the error of [`Codec::validate`] if it rejects them.
+    fn debug<E: Codec>(
+        name: &str,
+        bytes: &[u8],
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        let mut tuple = f.debug_tuple(name);
+        match E::validate(bytes) {
+            Ok(()) => tuple.field(&format_args!("\"{}\"", Encoded::<E>::new(bytes))),
+            Err(error) => tuple.field(&format_args!("<{error}>")),
+        };
+        tuple.finish()
+    }
+
+    /// Implements the traits that only depend on the wrapped value.
+    macro_rules! impl_wrapper {
+        ($name: ident <T $(, $param: ident)*>) => {
+            impl<T $(, $param)*> $name<T $(, $param)*> {
+                /// Wrap `value`.
+                pub const fn new(value: T) -> Self {
+                    $name(value, PhantomData)
+                }
+
+                /// Unwrap the value.
+                pub fn into_inner(self) -> T {
+                    self.0
+                }
+            }
+
+            impl<T $(, $param)*> From<T> for $name<T $(, $param)*> {
+                fn from(value: T) -> Self {
+                    $name::new(value)
+                }
+            }
+
+            impl<T $(, $param)*> core::ops::Deref for $name<T $(, $param)*> {
+                type Target = T;
+
+                fn deref(&self) -> &T {
+                    &self.0
+                }
+            }
+
+            impl<T $(, $param)*> core::ops::DerefMut for $name<T $(, $param)*> {
+                fn deref_mut(&mut self) -> &mut T {
+                    &mut self.0
+                }
+            }
+
+            impl<T: Clone $(, $param)*> Clone for $name<T $(, $param)*> {
+                fn clone(&self) -> Self {
+                    $name::new(self.0.clone())
+                }
+            }
+
+            impl<T: Copy $(, $param)*> Copy for $name<T $(, $param)*> {}
+
+            impl<T: Default $(, $param)*> Default for $name<T $(, $param)*> {
+                fn default() -> Self {
+                    $name::new(T::default())
+                }
+            }
+
+            impl<T: PartialEq $(, $param)*> PartialEq for $name<T $(, $param)*> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.0 == other.0
+                }
+            }
+
+            impl<T: Eq $(, $param)*> Eq for $name<T $(, $param)*> {}
+
+            impl<T: PartialOrd $(, $param)*> PartialOrd for $name<T $(, $param)*> {
+                fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                    self.0.partial_cmp(&other.0)
+                }
+            }
+
+            impl<T: Ord $(, $param)*> Ord for $name<T $(, $param)*> {
+                fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                    self.0.cmp(&other.0)
+                }
+            }
+
+            impl<T: core::hash::Hash $(, $param)*> core::hash::Hash for $name<T $(, $param)*> {
+                fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                    self.0.hash(state)
+                }
+            }
+        };
+    }
+
+    /// Implements [`Display`], [`Debug`] and [`FromStr`] with the encoded form of `[U]`.
+    macro_rules! impl_encoded {
+        ($name: ident) => {
+            impl<T: Borrow<[U]>, E: Codec, U: NoUninit> Display for $name<T, E, U> {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    display::<E>(bytemuck::cast_slice(self.0.borrow()), f)
+                }
+            }
+
+            impl<T: Borrow<[U]>, E: Codec, U: NoUninit> Debug for $name<T, E, U> {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    debug::<E>(stringify!($name), bytemuck::cast_slice(self.0.borrow()), f)
+                }
+            }
+
+            #[cfg(feature = "alloc")]
+            impl<T, E, U> FromStr for $name<T, E, U>
+            where
+                T: for<'t> TryFrom<&'t [U], Error: Display>,
+                E: Codec,
+                U: AnyBitPattern,
+            {
+                type Err = DecodeError;
+
+                fn from_str(s: &str) -> Result<Self, DecodeError> {
+                    let decoded = decode_typed::<E, U>(s.as_bytes())?;
+                    DecodeError::convert(&decoded).map($name::new)
+                }
+            }
+        };
+    }
+
+    /// A wrapper that serializes `T` like [base64](crate::base64),
+    /// for places where `#[serde(with)]` cannot be used, like generics or `Vec<Base64<T>>`.
+    ///
+    /// `E` is the [`Codec`] and `U` the element type of `T`.
+    ///
+    /// [`Display`] and [`Debug`] show the encoded form, [`FromStr`](core::str::FromStr) decodes it.
+    /// If [`Codec::validate`] rejects the value, [`Display`] fails and [`Debug`] shows the error instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use serde::{Serialize, Deserialize};
+    /// use serde_repr_base64::{engine::Standard, Base64};
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Keys {
+    ///     keys: Vec<Base64<[u8; 4]>>,
+    ///     words: Base64<Vec<u16>, Standard, u16>,
+    /// }
+    /// ```
+    #[repr(transparent)]
+    pub struct Base64<T, E = UrlSafe, U = u8>(pub T, PhantomData<fn() -> (E, U)>);
+
+    impl_wrapper!(Base64<T, E, U>);
+    impl_encoded!(Base64);
+
+    impl<T: Borrow<[U]>, E: Codec, U: NoUninit> Serialize for Base64<T, E, U> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            crate::codec::bytes::With::<E>::serialize(&self.0, serializer)
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<'de, T, E, U> Deserialize<'de> for Base64<T, E, U>
+    where
+        T: for<'t> TryFrom<&'t [U], Error: Display>,
+        E: Codec,
+        U: AnyBitPattern,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            crate::codec::bytes::With::<E>::deserialize(deserializer).map(Base64::new)
+        }
+    }
+
+    /// A wrapper for map keys that serializes `T` like [base64::map_keys](crate::base64::map_keys),
+    /// as a `base64` string in human readable formats and with its own implementation in binary formats.
+    ///
+    /// `E` is the [`Codec`] and `U` the element type of `T`.
+    ///
+    /// [`Display`] and [`Debug`] show the encoded form, [`FromStr`](core::str::FromStr) decodes it.
+    ///
+    /// This requires the `alloc` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use serde::{Serialize, Deserialize};
+    /// # use std::collections::HashMap;
+    /// use serde_repr_base64::Base64Key;
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Records {
+    ///     by_hash: HashMap<Base64Key<[u8; 32]>, String>,
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[repr(transparent)]
+    pub struct Base64Key<T, E = UrlSafe, U = u8>(pub T, PhantomData<fn() -> (E, U)>);
+
+    #[cfg(feature = "alloc")]
+    impl_wrapper!(Base64Key<T, E, U>);
+    #[cfg(feature = "alloc")]
+    impl_encoded!(Base64Key);
+
+    #[cfg(feature = "alloc")]
+    impl<T, E, U> Serialize for Base64Key<T, E, U>
+    where
+        T: Borrow<[U]> + Serialize,
+        E: Codec,
+        U: NoUninit,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            crate::codec::bytes::map_keys::serialize_key::<S, E, T, U>(&self.0, serializer)
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<'de, T, E, U> Deserialize<'de> for Base64Key<T, E, U>
+    where
+        T: for<'t> TryFrom<&'t [U], Error: Display> + Deserialize<'de>,
+        E: Codec,
+        U: AnyBitPattern,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            crate::codec::bytes::map_keys::deserialize_key::<D, E, T, U>(deserializer)
+                .map(Base64Key::new)
+        }
+    }
+
+    /// A wrapper that serializes `T` like [base64_string](crate::base64_string),
+    /// for places where `#[serde(with)]` cannot be used.
+    ///
+    /// `E` is the [`Codec`].
+    ///
+    /// [`Display`] and [`Debug`] show the encoded form, [`FromStr`] decodes it.
+    ///
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    #[repr(transparent)]
+    pub struct Base64String<T, E = UrlSafe>(pub T, PhantomData<fn() -> E>);
+
+    #[cfg(feature = "alloc")]
+    impl_wrapper!(Base64String<T, E>);
+
+    #[cfg(feature = "alloc")]
+    impl<T: AsRef<str>, E: Codec> Display for Base64String<T, E> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            display::<E>(self.0.as_ref().as_bytes(), f)
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<T: AsRef<str>, E: Codec> Debug for Base64String<T, E> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            debug::<E>("Base64String", self.0.as_ref().as_bytes(), f)
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<T: TryFrom<String, Error: Display>, E: Codec> FromStr for Base64String<T, E> {
+        type Err = DecodeError;
+
+        fn from_str(s: &str) -> Result<Self, DecodeError> {
+            let string = String::from_utf8(decode_typed::<E, u8>(s.as_bytes())?).map_err(|e| {
+                DecodeError::InvalidUtf8 {
+                    offset: e.utf8_error().valid_up_to(),
+                }
+            })?;
+            T::try_from(string)
+                .map(Base64String::new)
+                .map_err(DecodeError::conversion)
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<T: AsRef<str>, E: Codec> Serialize for Base64String<T, E> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            crate::codec::string::With::<E>::serialize(&self.0, serializer)
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<'de, T: TryFrom<String, Error: Display>, E: Codec> Deserialize<'de> for Base64String<T, E> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            crate::codec::string::With::<E>::deserialize(deserializer).map(Base64String::new)
+        }
     }
 }
 
-/// A `#[serde(with)]` adaptor that converts an array into a `base64` string.
-///
-/// This supports types that implement [`Borrow<[T]>`](std::borrow::Borrow) and [`TryFrom<&[T]>`](std::convert::TryFrom)
-/// and `T` implements [`bytemuck::AnyBitPattern`].
-pub mod base64 {
-    use std::{
-        borrow::{Borrow, Cow},
-        fmt::Display,
+mod backend {
+    use core::{
+        fmt::{Display, Write},
+        marker::PhantomData,
     };
 
-    use base64::{engine::general_purpose::URL_SAFE, Engine};
-    use bytemuck::{AnyBitPattern, NoUninit};
-    use serde::{Deserialize, Deserializer, Serializer};
+    use base64::{display::Base64Display, engine::Config, DecodeSliceError, Engine};
+    use serde::Serializer;
 
-    #[doc(hidden)]
-    pub fn serialize<S: Serializer, T: Borrow<[U]>, U: NoUninit>(
-        item: &T,
+    use crate::codec::Codec;
+    use crate::{Base64Engine, DecodeError};
+
+    /// Formats `bytes` using the codec `C`, without allocating.
+    pub struct Encoded<'t, C>(&'t [u8], PhantomData<C>);
+
+    impl<'t, C: Codec> Encoded<'t, C> {
+        pub fn new(bytes: &'t [u8]) -> Self {
+            Encoded(bytes, PhantomData)
+        }
+    }
+
+    impl<C: Codec> Display for Encoded<'_, C> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            C::encode(self.0, f)
+        }
+    }
+
+    /// Serialize `bytes` as a string using the codec `C`, after [`Codec::validate`].
+    pub fn collect_encoded<C: Codec, S: Serializer>(
+        bytes: &[u8],
         serializer: S,
     ) -> Result<S::Ok, S::Error> {
-        let slice: &[u8] = bytemuck::cast_slice(item.borrow());
-        serializer.serialize_str(&URL_SAFE.encode(slice))
+        C::validate(bytes).map_err(serde::ser::Error::custom)?;
+        serializer.collect_str(&Encoded::<C>::new(bytes))
     }
 
-    #[doc(hidden)]
-    pub fn deserialize<
-        'de,
-        D: Deserializer<'de>,
-        T: for<'t> TryFrom<&'t [U], Error: Display> + Deserialize<'de>,
-        U: AnyBitPattern + Copy,
-    >(
-        deserializer: D,
-    ) -> Result<T, D::Error> {
-        let s = <Cow<str>>::deserialize(deserializer)?;
-        let Ok(decoded) = URL_SAFE.decode(s.as_bytes()) else {
-            return Err(serde::de::Error::custom(format!(
-                "{s} is not a valid utf-8 string"
-            )));
+    /// The encoded length of `len` bytes using the engine `E`.
+    pub fn encoded_len<E: Base64Engine>(len: usize) -> Option<usize> {
+        base64::encoded_len(len, E::ENGINE.config().encode_padding())
+    }
+
+    /// Write `bytes` as `base64` using the engine `E`.
+    pub fn encode<E: Base64Engine, W: Write>(bytes: &[u8], out: &mut W) -> core::fmt::Result {
+        #[cfg(feature = "simd")]
+        if let Some(simd) = E::SIMD {
+            // A multiple of 3 so only the last chunk can be padded.
+            const CHUNK: usize = 3 * 1024;
+            let mut buffer = [0; CHUNK / 3 * 4];
+            for chunk in bytes.chunks(CHUNK) {
+                out.write_str(
+                    simd.encode_as_str(chunk, base64_simd::Out::from_slice(&mut buffer)),
+                )?;
+            }
+            return Ok(());
+        }
+        write!(out, "{}", Base64Display::new(bytes, &E::ENGINE))
+    }
+
+    /// Write `ascii` to `out`.
+    pub fn write_ascii<W: Write>(ascii: &[u8], out: &mut W) -> core::fmt::Result {
+        debug_assert!(ascii.is_ascii());
+        // Safety: every codec writes ascii symbols only, which is valid utf-8.
+        out.write_str(unsafe { core::str::from_utf8_unchecked(ascii) })
+    }
+
+    /// Decode `input` into `output` using the engine `E`.
+    pub fn decode<E: Base64Engine>(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+        #[cfg(feature = "simd")]
+        if let Some(simd) = E::SIMD {
+            // The scalar engine reports the precise error when `base64_simd` fails.
+            if let Ok(len) = simd.decoded_length(input) {
+                if len > output.len() {
+                    return Err(DecodeError::InvalidEncodedLength { len: input.len() });
+                }
+                if let Ok(decoded) = simd.decode(input, base64_simd::Out::from_slice(output)) {
+                    return Ok(decoded.len());
+                }
+            }
+        }
+        match E::ENGINE.decode_slice(input, output) {
+            Ok(len) => Ok(len),
+            Err(DecodeSliceError::DecodeError(e)) => Err(e.into()),
+            Err(DecodeSliceError::OutputSliceTooSmall) => {
+                Err(DecodeError::InvalidEncodedLength { len: input.len() })
+            }
+        }
+    }
+}
+
+/// Radix 85 encoding shared by [z85] and [ascii85].
+mod base85 {
+    use core::fmt::Write;
+
+    use crate::{backend, DecodeError};
+
+    /// The symbols of an alphabet.
+    pub struct Spec {
+        symbols: &'static [u8; 85],
+        /// The value of each byte, or [`INVALID`].
+        values: [u8; 256],
+        /// The symbol written for 4 zero bytes instead of 5 zero symbols.
+        zero: Option<u8>,
+    }
+
+    const INVALID: u8 = 0xff;
+
+    impl Spec {
+        pub const fn new(symbols: &'static [u8; 85], zero: Option<u8>) -> Self {
+            let mut values = [INVALID; 256];
+            let mut index = 0;
+            while index < symbols.len() {
+                values[symbols[index] as usize] = index as u8;
+                index += 1;
+            }
+            Spec {
+                symbols,
+                values,
+                zero,
+            }
+        }
+    }
+
+    /// An upper bound of the encoded length of `len` bytes.
+    pub fn encoded_len(len: usize) -> usize {
+        let tail = len % 4;
+        (len / 4)
+            .saturating_mul(5)
+            .saturating_add(if tail == 0 { 0 } else { tail + 1 })
+    }
+
+    /// The decoded length of `len` symbols, without abbreviations.
+    pub fn decoded_len(len: usize) -> usize {
+        len / 5 * 4 + (len % 5).saturating_sub(1)
+    }
+
+    pub fn encode<W: Write>(bytes: &[u8], spec: &Spec, out: &mut W) -> core::fmt::Result {
+        const CHUNK: usize = 4 * 256;
+        let mut buffer = [0; CHUNK / 4 * 5];
+        for chunk in bytes.chunks(CHUNK) {
+            let mut len = 0;
+            for group in chunk.chunks(4) {
+                let mut block = [0; 4];
+                block[..group.len()].copy_from_slice(group);
+                let mut value = u32::from_be_bytes(block);
+                if let (Some(zero), 0, 4) = (spec.zero, value, group.len()) {
+                    buffer[len] = zero;
+                    len += 1;
+                    continue;
+                }
+                let symbols = &mut buffer[len..len + 5];
+                for symbol in symbols.iter_mut().rev() {
+                    *symbol = spec.symbols[(value % 85) as usize];
+                    value /= 85;
+                }
+                // A partial group of `n` bytes is written as its first `n + 1` symbols.
+                len += group.len() + 1;
+            }
+            backend::write_ascii(&buffer[..len], out)?;
+        }
+        Ok(())
+    }
+
+    pub fn decode(input: &[u8], spec: &Spec, output: &mut [u8]) -> Result<usize, DecodeError> {
+        let mut written = 0;
+        let mut group = [0u8; 5];
+        let mut count = 0;
+        let mut write = |bytes: &[u8], output: &mut [u8]| -> Result<(), DecodeError> {
+            let target = output
+                .get_mut(written..written + bytes.len())
+                .ok_or(DecodeError::InvalidEncodedLength { len: input.len() })?;
+            target.copy_from_slice(bytes);
+            written += bytes.len();
+            Ok(())
         };
-        let slice: &[u8] = bytemuck::cast_slice(&decoded);
-        T::try_from(bytemuck::try_cast_slice::<_, U>(slice).map_err(serde::de::Error::custom)?)
-            .map_err(serde::de::Error::custom)
+        for (offset, symbol) in input.iter().enumerate() {
+            if spec.zero == Some(*symbol) && count == 0 {
+                write(&[0; 4], output)?;
+                continue;
+            }
+            let value = spec.values[usize::from(*symbol)];
+            if value == INVALID {
+                return Err(DecodeError::InvalidSymbol { offset });
+            }
+            group[count] = value;
+            count += 1;
+            if count == 5 {
+                write(&group_value(&group, offset - 4)?.to_be_bytes(), output)?;
+                count = 0;
+            }
+        }
+        match count {
+            0 => {}
+            1 => return Err(DecodeError::InvalidEncodedLength { len: input.len() }),
+            _ => {
+                // Pad with the highest symbol, so truncating gives back the original bytes.
+                group[count..].fill(84);
+                let value = group_value(&group, input.len() - count)?;
+                write(&value.to_be_bytes()[..count - 1], output)?;
+            }
+        }
+        Ok(written)
+    }
+
+    /// The value of a group of 5 symbols starting at `offset`.
+    fn group_value(group: &[u8; 5], offset: usize) -> Result<u32, DecodeError> {
+        let value = group
+            .iter()
+            .fold(0u64, |value, x| value * 85 + u64::from(*x));
+        u32::try_from(value).map_err(|_| DecodeError::InvalidSymbol { offset })
     }
 }
 
-/// A `#[serde(with)]` adaptor that converts an array into a `base64` string only
-/// in human readable formats like `json` but not in binary formats like `postcard`.
-///
-/// This supports types that implement [`Borrow<[T]>`](std::borrow::Borrow) and [`TryFrom<&[T]>`](std::convert::TryFrom)
-/// and `T` implements [`bytemuck::AnyBitPattern`].
-pub mod base64_if_readable {
-    use std::{
-        borrow::{Borrow, Cow},
-        fmt::Display,
+#[cfg(feature = "alloc")]
+mod combinator {
+    use core::marker::PhantomData;
+
+    use serde::{
+        de::{MapAccess, SeqAccess, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
     };
 
-    use base64::{engine::general_purpose::URL_SAFE, Engine};
-    use bytemuck::{AnyBitPattern, NoUninit};
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    /// Serializes an element with the adaptor `A`, `U` is the element type of `T` if there is one.
+    pub struct Ser<'t, A, T, U = ()>(pub &'t T, PhantomData<(A, U)>);
+
+    impl<'t, A, T, U> Ser<'t, A, T, U> {
+        pub fn new(item: &'t T) -> Self {
+            Ser(item, PhantomData)
+        }
+    }
+
+    /// Deserializes an element with the adaptor `A`.
+    pub struct De<A, T, U = ()>(pub T, PhantomData<(A, U)>);
+
+    impl<A, T, U> De<A, T, U> {
+        pub fn new(item: T) -> Self {
+            De(item, PhantomData)
+        }
+    }
+
+    /// The adaptor that uses the [`Serialize`] and [`Deserialize`] implementations of `T`.
+    pub struct Plain;
+
+    impl<T: Serialize> Serialize for Ser<'_, Plain, T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for De<Plain, T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            T::deserialize(deserializer).map(De::new)
+        }
+    }
 
-    #[doc(hidden)]
-    pub fn serialize<S: Serializer, T: Borrow<[U]> + Serialize, U: NoUninit>(
-        item: &T,
+    pub fn serialize_option<S: Serializer, A, T, U>(
+        item: &Option<T>,
         serializer: S,
-    ) -> Result<S::Ok, S::Error> {
-        if serializer.is_human_readable() {
-            let slice: &[u8] = bytemuck::cast_slice(item.borrow());
-            serializer.serialize_str(&URL_SAFE.encode(slice))
-        } else {
-            item.serialize(serializer)
+    ) -> Result<S::Ok, S::Error>
+    where
+        for<'t> Ser<'t, A, T, U>: Serialize,
+    {
+        match item {
+            Some(item) => serializer.serialize_some(&Ser::<A, T, U>::new(item)),
+            None => serializer.serialize_none(),
         }
     }
 
-    #[doc(hidden)]
-    pub fn deserialize<
+    pub fn deserialize_option<'de, D: Deserializer<'de>, A, T, U>(
+        deserializer: D,
+    ) -> Result<Option<T>, D::Error>
+    where
+        De<A, T, U>: Deserialize<'de>,
+    {
+        Ok(Option::<De<A, T, U>>::deserialize(deserializer)?.map(|x| x.0))
+    }
+
+    pub fn serialize_seq<S: Serializer, A, C, T, U>(
+        items: &C,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        for<'t> &'t C: IntoIterator<Item = &'t T>,
+        for<'t> Ser<'t, A, T, U>: Serialize,
+    {
+        serializer.collect_seq(items.into_iter().map(Ser::<A, T, U>::new))
+    }
+
+    pub fn deserialize_seq<'de, D: Deserializer<'de>, A, C: FromIterator<T>, T, U>(
+        deserializer: D,
+    ) -> Result<C, D::Error>
+    where
+        De<A, T, U>: Deserialize<'de>,
+    {
+        deserializer.deserialize_seq(SeqVisitor::<A, C, T, U>(PhantomData))
+    }
+
+    /// Serializes a map, the keys with the adaptor `KA` and the values with the adaptor `VA`.
+    pub fn serialize_map<S: Serializer, M, K, V, KA, KU, VA, VU>(
+        items: &M,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        for<'t> &'t M: IntoIterator<Item = (&'t K, &'t V)>,
+        for<'t> Ser<'t, KA, K, KU>: Serialize,
+        for<'t> Ser<'t, VA, V, VU>: Serialize,
+    {
+        serializer.collect_map(
+            items
+                .into_iter()
+                .map(|(key, value)| (Ser::<KA, K, KU>::new(key), Ser::<VA, V, VU>::new(value))),
+        )
+    }
+
+    /// Deserializes a map, the keys with the adaptor `KA` and the values with the adaptor `VA`.
+    pub fn deserialize_map<
         'de,
         D: Deserializer<'de>,
-        T: for<'t> TryFrom<&'t [U], Error: Display> + Deserialize<'de>,
-        U: AnyBitPattern + Copy,
+        M: FromIterator<(K, V)>,
+        K,
+        V,
+        KA,
+        KU,
+        VA,
+        VU,
     >(
         deserializer: D,
-    ) -> Result<T, D::Error> {
-        if deserializer.is_human_readable() {
-            let s = <Cow<str>>::deserialize(deserializer)?;
-            let Ok(decoded) = URL_SAFE.decode(s.as_bytes()) else {
-                return Err(serde::de::Error::custom(format!(
-                    "{s} is not a valid utf-8 string"
-                )));
-            };
-            let slice: &[u8] = bytemuck::cast_slice(&decoded);
-            T::try_from(bytemuck::try_cast_slice::<_, U>(slice).map_err(serde::de::Error::custom)?)
-                .map_err(serde::de::Error::custom)
+    ) -> Result<M, D::Error>
+    where
+        De<KA, K, KU>: Deserialize<'de>,
+        De<VA, V, VU>: Deserialize<'de>,
+    {
+        deserializer.deserialize_map(MapVisitor::<M, De<KA, K, KU>, De<VA, V, VU>>(PhantomData))
+    }
+
+    struct SeqVisitor<A, C, T, U>(PhantomData<(A, C, T, U)>);
+
+    impl<'de, A, C: FromIterator<T>, T, U> Visitor<'de> for SeqVisitor<A, C, T, U>
+    where
+        De<A, T, U>: Deserialize<'de>,
+    {
+        type Value = C;
+
+        fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+            formatter.write_str("a sequence")
+        }
+
+        fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+            // Collect directly into `C` and stop at the first error.
+            let mut error = None;
+            let items = core::iter::from_fn(|| match seq.next_element::<De<A, T, U>>() {
+                Ok(item) => item.map(|x| x.0),
+                Err(e) => {
+                    error = Some(e);
+                    None
+                }
+            })
+            .collect();
+            error.map_or(Ok(items), Err)
+        }
+    }
+
+    struct MapVisitor<M, K, V>(PhantomData<(M, K, V)>);
+
+    impl<'de, M, KA, K, KU, VA, V, VU> Visitor<'de> for MapVisitor<M, De<KA, K, KU>, De<VA, V, VU>>
+    where
+        M: FromIterator<(K, V)>,
+        De<KA, K, KU>: Deserialize<'de>,
+        De<VA, V, VU>: Deserialize<'de>,
+    {
+        type Value = M;
+
+        fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+            formatter.write_str("a map")
+        }
+
+        fn visit_map<S: MapAccess<'de>>(self, mut map: S) -> Result<Self::Value, S::Error> {
+            // See `SeqVisitor`.
+            let mut error = None;
+            let items =
+                core::iter::from_fn(|| match map.next_entry::<De<KA, K, KU>, De<VA, V, VU>>() {
+                    Ok(entry) => entry.map(|(key, value)| (key.0, value.0)),
+                    Err(e) => {
+                        error = Some(e);
+                        None
+                    }
+                })
+                .collect();
+            error.map_or(Ok(items), Err)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod visitor {
+    use serde::de::Visitor;
+
+    use crate::DecodeError;
+
+    /// Decodes a string in `visit_str`, so borrowed strings do not need to be copied into a `String`.
+    pub struct StrVisitor<F>(F);
+
+    impl<F> StrVisitor<F> {
+        pub fn new<V>(decode: F) -> Self
+        where
+            F: FnOnce(&str) -> Result<V, DecodeError>,
+        {
+            StrVisitor(decode)
+        }
+    }
+
+    impl<'de, V, F: FnOnce(&str) -> Result<V, DecodeError>> Visitor<'de> for StrVisitor<F> {
+        type Value = V;
+
+        fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+            formatter.write_str("an encoded string")
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            (self.0)(v).map_err(E::custom)
+        }
+    }
+}
+
+mod typed {
+    #[cfg(feature = "alloc")]
+    use alloc::vec::Vec;
+    use core::mem::{size_of, MaybeUninit};
+
+    use bytemuck::AnyBitPattern;
+
+    use crate::codec::Codec;
+    use crate::DecodeError;
+
+    /// Decode `input` directly into a buffer of `U`,
+    /// unlike casting a `Vec<u8>` this does not depend on the alignment of the allocation.
+    #[cfg(feature = "alloc")]
+    pub fn decode_typed<C: Codec, U: AnyBitPattern>(input: &[u8]) -> Result<Vec<U>, DecodeError> {
+        let size = size_of::<U>();
+        let estimate = C::decoded_len_estimate(input);
+        if size == 0 {
+            return Err(DecodeError::InvalidLength {
+                len: estimate,
+                element_size: size,
+            });
+        }
+        let mut buffer = bytemuck::zeroed_vec::<U>(estimate.div_ceil(size));
+        // Safety: `zeroed_vec` allocates zeroed memory, so every byte of the buffer,
+        // including padding bytes, is initialized. `U` is valid for any bit pattern.
+        let bytes = unsafe {
+            core::slice::from_raw_parts_mut(buffer.as_mut_ptr().cast::<u8>(), buffer.len() * size)
+        };
+        let len = C::decode(input, bytes)?;
+        if len % size != 0 {
+            return Err(DecodeError::InvalidLength {
+                len,
+                element_size: size,
+            });
+        }
+        buffer.truncate(len / size);
+        Ok(buffer)
+    }
+
+    /// Check the number of decoded bytes fits exactly in `[U; N]`.
+    fn check_array_len<U, const N: usize>(len: usize) -> Result<(), DecodeError> {
+        let size = size_of::<U>();
+        if len == N * size {
+            Ok(())
+        } else if size == 0 || len % size != 0 {
+            Err(DecodeError::InvalidLength {
+                len,
+                element_size: size,
+            })
         } else {
-            T::deserialize(deserializer)
+            Err(DecodeError::WrongLength {
+                expected: N,
+                got: len / size,
+            })
+        }
+    }
+
+    /// Decode `input` directly into `[U; N]` without allocating.
+    ///
+    /// The length of the input is validated before decoding if the codec knows the decoded length.
+    pub fn decode_array<C: Codec, U: AnyBitPattern, const N: usize>(
+        input: &[u8],
+    ) -> Result<[U; N], DecodeError> {
+        if let Some(len) = C::decoded_len(input) {
+            check_array_len::<U, N>(len)?;
+        }
+        let mut array = MaybeUninit::<[U; N]>::zeroed();
+        // Safety: `array` is zeroed in place, so every byte is initialized.
+        let bytes = unsafe {
+            core::slice::from_raw_parts_mut(array.as_mut_ptr().cast::<u8>(), size_of::<[U; N]>())
+        };
+        check_array_len::<U, N>(C::decode(input, bytes)?)?;
+        // Safety: every byte is initialized and `U` is valid for any bit pattern.
+        Ok(unsafe { array.assume_init() })
+    }
+
+    /// Copy `bytes` into `[U; N]` without allocating.
+    pub fn copy_array<U: AnyBitPattern, const N: usize>(
+        bytes: &[u8],
+    ) -> Result<[U; N], DecodeError> {
+        check_array_len::<U, N>(bytes.len())?;
+        let mut array = MaybeUninit::<[U; N]>::zeroed();
+        // Safety: see `decode_array`.
+        unsafe {
+            core::slice::from_raw_parts_mut(array.as_mut_ptr().cast::<u8>(), bytes.len())
+                .copy_from_slice(bytes);
+            Ok(array.assume_init())
+        }
+    }
+
+    /// Copy `bytes` into a buffer of `U`,
+    /// unlike casting the slice this does not depend on the alignment of `bytes`.
+    #[cfg(feature = "alloc")]
+    pub fn copy_typed<U: AnyBitPattern>(bytes: &[u8]) -> Result<Vec<U>, DecodeError> {
+        let size = size_of::<U>();
+        if size == 0 || bytes.len() % size != 0 {
+            return Err(DecodeError::InvalidLength {
+                len: bytes.len(),
+                element_size: size,
+            });
+        }
+        let mut buffer = bytemuck::zeroed_vec::<U>(bytes.len() / size);
+        // Safety: see `decode_typed`.
+        unsafe {
+            core::slice::from_raw_parts_mut(buffer.as_mut_ptr().cast::<u8>(), bytes.len())
+                .copy_from_slice(bytes);
+        }
+        Ok(buffer)
+    }
+}
+
+/// Elements that can be written in a fixed byte order by [base64_le] and [base64_be].
+///
+/// This is implemented for all primitive integers and floats.
+///
+/// `usize` and `isize` are always written as 64 bit integers so the output does not
+/// depend on the pointer width, decoding a value that does not fit on the target is an error.
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "
+
+[base64_le]: crate#features
+[base64_be]: #features"
+)]
+pub trait Scalar: Copy {
+    /// Size of the element in bytes.
+    const SIZE: usize;
+
+    /// Write the element as little endian bytes, `out` has length [`Scalar::SIZE`].
+    fn write_le(self, out: &mut [u8]);
+    /// Write the element as big endian bytes, `out` has length [`Scalar::SIZE`].
+    fn write_be(self, out: &mut [u8]);
+    /// Read the element from little endian bytes, `bytes` has length [`Scalar::SIZE`].
+    ///
+    /// Returns `None` if the value is not representable.
+    fn read_le(bytes: &[u8]) -> Option<Self>;
+    /// Read the element from big endian bytes, `bytes` has length [`Scalar::SIZE`].
+    ///
+    /// Returns `None` if the value is not representable.
+    fn read_be(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_scalar {
+    ($($ty: ty),*) => {
+        $(impl Scalar for $ty {
+            const SIZE: usize = core::mem::size_of::<$ty>();
+
+            fn write_le(self, out: &mut [u8]) {
+                out.copy_from_slice(&self.to_le_bytes())
+            }
+
+            fn write_be(self, out: &mut [u8]) {
+                out.copy_from_slice(&self.to_be_bytes())
+            }
+
+            fn read_le(bytes: &[u8]) -> Option<Self> {
+                let mut buf = [0; core::mem::size_of::<$ty>()];
+                buf.copy_from_slice(bytes);
+                Some(Self::from_le_bytes(buf))
+            }
+
+            fn read_be(bytes: &[u8]) -> Option<Self> {
+                let mut buf = [0; core::mem::size_of::<$ty>()];
+                buf.copy_from_slice(bytes);
+                Some(Self::from_be_bytes(buf))
+            }
+        })*
+    };
+}
+
+impl_scalar!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+macro_rules! impl_scalar_pointer_width {
+    ($($ty: ty => $fixed: ty),*) => {
+        $(impl Scalar for $ty {
+            const SIZE: usize = <$fixed as Scalar>::SIZE;
+
+            fn write_le(self, out: &mut [u8]) {
+                (self as $fixed).write_le(out)
+            }
+
+            fn write_be(self, out: &mut [u8]) {
+                (self as $fixed).write_be(out)
+            }
+
+            fn read_le(bytes: &[u8]) -> Option<Self> {
+                <$fixed>::read_le(bytes).and_then(|x| x.try_into().ok())
+            }
+
+            fn read_be(bytes: &[u8]) -> Option<Self> {
+                <$fixed>::read_be(bytes).and_then(|x| x.try_into().ok())
+            }
+        })*
+    };
+}
+
+impl_scalar_pointer_width!(usize => u64, isize => i64);
+
+#[cfg(feature = "alloc")]
+mod endian {
+    use alloc::{vec, vec::Vec};
+
+    use crate::{DecodeError, Scalar};
+
+    #[derive(Debug, Clone, Copy)]
+    pub enum ByteOrder {
+        Little,
+        Big,
+    }
+
+    pub fn to_bytes<U: Scalar>(items: &[U], order: ByteOrder) -> Vec<u8> {
+        let mut bytes = vec![0; items.len() * U::SIZE];
+        for (item, chunk) in items.iter().zip(bytes.chunks_exact_mut(U::SIZE)) {
+            match order {
+                ByteOrder::Little => item.write_le(chunk),
+                ByteOrder::Big => item.write_be(chunk),
+            }
         }
+        bytes
     }
+
+    pub fn from_bytes<U: Scalar>(bytes: &[u8], order: ByteOrder) -> Result<Vec<U>, DecodeError> {
+        if bytes.len() % U::SIZE != 0 {
+            return Err(DecodeError::InvalidLength {
+                len: bytes.len(),
+                element_size: U::SIZE,
+            });
+        }
+        bytes
+            .chunks_exact(U::SIZE)
+            .enumerate()
+            .map(|(index, chunk)| {
+                match order {
+                    ByteOrder::Little => U::read_le(chunk),
+                    ByteOrder::Big => U::read_be(chunk),
+                }
+                .ok_or(DecodeError::OutOfRange { index })
+            })
+            .collect()
+    }
+}
+
+/// Generates a module with the representation of [base64] that writes every element in the byte
+/// order `$order` instead of the byte order of the machine.
+macro_rules! endian_module {
+    ($(#[$attr: meta])* $name: ident, $order: expr) => {
+        $(#[$attr])*
+        ///
+        /// Unlike [base64], the output does not depend on the byte order of the machine.
+        ///
+        /// This supports types that implement [`Borrow<[T]>`](core::borrow::Borrow) and [`TryFrom<&[T]>`](core::convert::TryFrom)
+        /// and `T` implements [`Scalar`].
+        ///
+        #[doc = concat!("Use [`With`](", stringify!($name), "::With) to choose a different [`Base64Engine`] or [`Codec`].")]
+        ///
+        /// This requires the `alloc` feature.
+        #[cfg(feature = "alloc")]
+        pub mod $name {
+            use core::{borrow::Borrow, fmt::Display, marker::PhantomData};
+
+            use crate::backend::collect_encoded;
+            use serde::{Deserializer, Serializer};
+
+            use crate::codec::Codec;
+            use crate::endian::{from_bytes, to_bytes, ByteOrder};
+            use crate::engine::UrlSafe;
+            use crate::typed::decode_typed;
+            use crate::visitor::StrVisitor;
+            use crate::{DecodeError, Scalar};
+
+            #[doc(hidden)]
+            pub fn serialize<S: Serializer, T: Borrow<[U]>, U: Scalar>(
+                item: &T,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                With::<UrlSafe>::serialize(item, serializer)
+            }
+
+            #[doc(hidden)]
+            pub fn deserialize<
+                'de,
+                D: Deserializer<'de>,
+                T: for<'t> TryFrom<&'t [U], Error: Display>,
+                U: Scalar,
+            >(
+                deserializer: D,
+            ) -> Result<T, D::Error> {
+                With::<UrlSafe>::deserialize(deserializer)
+            }
+
+            #[doc = concat!("A `#[serde(with = \"", stringify!($name), "::With::<E>\")]` adaptor using [`Codec`] `E`.")]
+            pub struct With<E: Codec>(PhantomData<E>);
+
+            impl<E: Codec> With<E> {
+                #[doc(hidden)]
+                pub fn serialize<S: Serializer, T: Borrow<[U]>, U: Scalar>(
+                    item: &T,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    let bytes = to_bytes(item.borrow(), $order);
+                    collect_encoded::<E, _>(&bytes, serializer)
+                }
+
+                #[doc(hidden)]
+                pub fn deserialize<
+                    'de,
+                    D: Deserializer<'de>,
+                    T: for<'t> TryFrom<&'t [U], Error: Display>,
+                    U: Scalar,
+                >(
+                    deserializer: D,
+                ) -> Result<T, D::Error> {
+                    let items = deserializer.deserialize_str(StrVisitor::new(|s| {
+                        from_bytes::<U>(&decode_typed::<E, u8>(s.as_bytes())?, $order)
+                    }))?;
+                    DecodeError::convert(items.as_slice()).map_err(serde::de::Error::custom)
+                }
+            }
+        }
+    };
 }
+
+endian_module!(
+    /// A `#[serde(with)]` adaptor that converts an array into a little endian `base64` string.
+    base64_le,
+    ByteOrder::Little
+);
+
+endian_module!(
+    /// A `#[serde(with)]` adaptor that converts an array into a big endian `base64` string.
+    base64_be,
+    ByteOrder::Big
+);
@@ -0,0 +1,85 @@
+#![cfg(feature = "base58")]
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use serde_repr_base64::{base58, base58_if_readable, multibase};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Base58Test {
+    #[serde(with = "base58")]
+    bitcoin: Vec<u8>,
+    #[serde(with = "base58::With::<base58::Ripple>")]
+    ripple: Vec<u8>,
+    #[serde(with = "base58::With::<base58::Flickr>")]
+    flickr: Vec<u8>,
+    #[serde(with = "base58::array::With::<base58::Check>")]
+    checked: [u8; 2],
+    #[serde(with = "base58_if_readable::seq::With::<base58::Check>")]
+    keys: Vec<[u8; 4]>,
+    #[serde(with = "base58::option")]
+    maybe: Option<Vec<u16>>,
+}
+
+#[test]
+pub fn test_base58() {
+    let value = Base58Test {
+        bitcoin: b"Hello World!".to_vec(),
+        ripple: b"Hello World!".to_vec(),
+        flickr: b"Hello World!".to_vec(),
+        checked: [0x2d, 0x31],
+        keys: vec![[0, 0, 1, 2]],
+        maybe: Some(vec![1, 2]),
+    };
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json["bitcoin"], "2NEpo7TZRRrLZSi2U");
+    assert_eq!(json["ripple"], "p4NFofTZRRiLZS5p7");
+    assert_eq!(json["flickr"], "2nePN7syqqRkyrH2t");
+    assert_eq!(json["checked"], "PWEu9GGN");
+    // Leading zeros are written as the first symbol of the alphabet.
+    assert!(json["keys"][0].as_str().unwrap().starts_with("11"));
+
+    let b: Base58Test = serde_json::from_value(json).unwrap();
+    assert_eq!(value, b);
+    let c = postcard::to_allocvec(&value).unwrap();
+    let c: Base58Test = postcard::from_bytes(&c).unwrap();
+    assert_eq!(value, c);
+
+    let checked = |json: serde_json::Value| {
+        base58::With::<base58::Check>::deserialize::<_, Vec<u8>, u8>(json)
+            .map_err(|e| e.to_string())
+    };
+    assert_eq!(checked(json!("PWEu9GGN")).unwrap(), [0x2d, 0x31]);
+    assert_eq!(checked(json!("PWEu9GGM")).unwrap_err(), "invalid checksum");
+    assert_eq!(checked(json!("1")).unwrap_err(), "invalid checksum");
+    assert_eq!(
+        checked(json!("PWEu0GGN")).unwrap_err(),
+        "invalid symbol at offset 4"
+    );
+
+    let error = base58::array::deserialize::<_, u8, 2>(json!("2NEpo7TZRRrLZSi2U")).unwrap_err();
+    assert_eq!(error.to_string(), "expected 2 elements, got 12");
+    let error = base58::array::With::<base58::Check>::deserialize::<_, u8, 4>(json!("PWEu9GGN"))
+        .unwrap_err();
+    assert_eq!(error.to_string(), "expected 4 elements, got 2");
+    let error = base58::array::deserialize::<_, u8, 2>(json!("2NEpo7TZ0")).unwrap_err();
+    assert_eq!(error.to_string(), "invalid symbol at offset 8");
+    let error = base58::array::deserialize::<_, u8, 4>(json!("5T")).unwrap_err();
+    assert_eq!(error.to_string(), "expected 4 elements, got 2");
+}
+
+#[test]
+pub fn test_multibase_base58() {
+    use multibase::Multibase;
+
+    let encoded = multibase::With::<Multibase<base58::Bitcoin>>::serialize(
+        b"yes mani !",
+        serde_json::value::Serializer,
+    )
+    .unwrap();
+    // Test vector from the multibase specification.
+    assert_eq!(encoded, "z7paNL19xttacUY");
+    for encoded in ["z7paNL19xttacUY", "Z7Pznk19XTTzBtx"] {
+        let decoded: Vec<u8> = multibase::deserialize(json!(encoded)).unwrap();
+        assert_eq!(decoded, b"yes mani !");
+    }
+}
@@ -0,0 +1,136 @@
+#![cfg(feature = "bech32")]
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use serde_repr_base64::bech32::{self, Bech32, Bech32m, Hrp};
+use serde_repr_base64::Base64;
+
+pub struct Npub;
+
+impl Hrp for Npub {
+    const HRP: &'static str = "npub";
+}
+
+pub struct Addr;
+
+impl Hrp for Addr {
+    const HRP: &'static str = "addr";
+}
+
+pub struct A;
+
+impl Hrp for A {
+    const HRP: &'static str = "a";
+}
+
+pub struct Invalid;
+
+impl Hrp for Invalid {
+    const HRP: &'static str = "Np Ub";
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Bech32Test {
+    #[serde(with = "bech32::array::With::<Bech32<Npub>>")]
+    public_key: [u8; 32],
+    #[serde(with = "bech32::With::<Bech32m<Npub>>")]
+    key_m: Vec<u8>,
+    #[serde(with = "bech32::option::With::<Bech32<Addr>>")]
+    address: Option<Vec<u8>>,
+}
+
+#[test]
+pub fn test_bech32() {
+    let value = Bech32Test {
+        public_key: core::array::from_fn(|x| x as u8),
+        key_m: (0..32).collect(),
+        address: Some(vec![1, 2, 3]),
+    };
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(
+        json["public_key"],
+        "npub1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0st5hsmq"
+    );
+    assert_eq!(
+        json["key_m"],
+        "npub1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0s7g8u7z"
+    );
+    assert_eq!(json["address"], "addr1qypqx805nky");
+
+    let b: Bech32Test = serde_json::from_value(json).unwrap();
+    assert_eq!(value, b);
+    let c = postcard::to_allocvec(&value).unwrap();
+    let c: Bech32Test = postcard::from_bytes(&c).unwrap();
+    assert_eq!(value, c);
+
+    // Test vectors from BIP-173 and BIP-350, upper case is accepted.
+    let empty: Vec<u8> = bech32::With::<Bech32<A>>::deserialize(json!("A12UEL5L")).unwrap();
+    assert!(empty.is_empty());
+    let empty: Vec<u8> = bech32::With::<Bech32m<A>>::deserialize(json!("a1lqfn3a")).unwrap();
+    assert!(empty.is_empty());
+
+    let error = |json: serde_json::Value| {
+        bech32::With::<Bech32<Addr>>::deserialize::<_, Vec<u8>, u8>(json)
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(error(json!("addr1qypqx805nkz")), "invalid checksum");
+    assert_eq!(error(json!("a12uel5l")), "invalid prefix");
+    assert_eq!(error(json!("qypqx805nky")), "invalid prefix");
+    assert_eq!(
+        error(json!("addr1qypqb805nky")),
+        "invalid symbol at offset 9"
+    );
+    assert_eq!(
+        error(json!("addr1qypQx805nky")),
+        "invalid symbol at offset 8"
+    );
+    // Bech32m checksums are rejected by Bech32.
+    let error =
+        bech32::With::<Bech32<A>>::deserialize::<_, Vec<u8>, u8>(json!("a1lqfn3a")).unwrap_err();
+    assert_eq!(error.to_string(), "invalid checksum");
+
+    let error =
+        bech32::array::With::<Bech32<Addr>>::deserialize::<_, u8, 4>(json!("addr1qypqx805nky"))
+            .unwrap_err();
+    assert_eq!(error.to_string(), "expected 4 elements, got 3");
+    // A different human readable part is reported before the length.
+    let error = bech32::array::With::<Bech32<Addr>>::deserialize::<_, u8, 4>(json!(
+        "npub1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0st5hsmq"
+    ))
+    .unwrap_err();
+    assert_eq!(error.to_string(), "invalid prefix");
+    let error =
+        bech32::array::With::<Bech32<Addr>>::deserialize::<_, u8, 4>(json!("ADDR1QYPQX805NKY"))
+            .unwrap_err();
+    assert_eq!(error.to_string(), "expected 4 elements, got 3");
+
+    // Payloads over the length limit and invalid human readable parts are errors, not panics.
+    let long = Bech32Test {
+        public_key: [0; 32],
+        key_m: vec![0; 2000],
+        address: None,
+    };
+    let error = serde_json::to_string(&long).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "encoded length 3211 exceeds the maximum of 1023"
+    );
+    let mut cbor = Vec::new();
+    assert!(ciborium::into_writer(&long, &mut cbor).is_err());
+    let error =
+        bech32::With::<Bech32<Invalid>>::serialize(&[1u8, 2], serde_json::value::Serializer)
+            .unwrap_err();
+    assert_eq!(error.to_string(), "invalid prefix");
+
+    // The wrapper shows the error in `Debug` and fails in `Display` instead of panicking.
+    let wrapped = Base64::<Vec<u8>, Bech32<Npub>>::new(vec![0; 2000]);
+    assert_eq!(
+        format!("{wrapped:?}"),
+        "Base64(<encoded length 3211 exceeds the maximum of 1023>)"
+    );
+    let mut out = String::new();
+    assert!(std::fmt::Write::write_fmt(&mut out, format_args!("{wrapped}")).is_err());
+    let wrapped = Base64::<Vec<u8>, Bech32<Addr>>::new(vec![1, 2, 3]);
+    assert_eq!(format!("{wrapped:?}"), r#"Base64("addr1qypqx805nky")"#);
+}
@@ -0,0 +1,46 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use serde::Deserialize;
+use serde_repr_base64::base64;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|x| x.set(x.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Keys {
+    #[serde(with = "base64::array")]
+    key: [u8; 32],
+    #[serde(with = "base64::array")]
+    words: [u64; 4],
+}
+
+#[test]
+pub fn test_array_no_alloc() {
+    let json = r#"{"key":"AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=","words":"AQAAAAAAAAACAAAAAAAAAAMAAAAAAAAABAAAAAAAAAA="}"#;
+    let before = ALLOCATIONS.with(Cell::get);
+    let keys: Keys = serde_json::from_str(json).unwrap();
+    let after = ALLOCATIONS.with(Cell::get);
+    assert_eq!(before, after);
+    assert_eq!(keys.key, core::array::from_fn(|i| i as u8));
+    assert_eq!(u64::from_le(keys.words[0]), 1);
+}
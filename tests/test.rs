@@ -1,7 +1,14 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Debug;
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use serde_repr_base64::{base64, base64_if_readable, base64_string};
+use serde_repr_base64::{
+    ascii85, base32, base32_if_readable, base32_string, base45, base64, base64_be,
+    base64_if_readable, base64_le, base64_string, codec,
+    engine::{Standard, StandardNoPad, UrlSafe, UrlSafeNoPad},
+    hex, hex_if_readable, hex_string, multibase, z85, Base64, Base64Engine, Base64Key,
+    Base64String, Codec, DecodeError, EncodeError,
+};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct BytesTest {
@@ -33,6 +40,251 @@ pub struct StringTest {
     str: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EngineTest {
+    #[serde(with = "base64::With::<Standard>")]
+    standard: [u8; 4],
+    #[serde(with = "base64_if_readable::With::<StandardNoPad>")]
+    standard_no_pad: Vec<u8>,
+    #[serde(with = "base64_string::With::<UrlSafeNoPad>")]
+    url_safe_no_pad: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct EndianTest {
+    #[serde(with = "base64_le")]
+    le: [u32; 2],
+    #[serde(with = "base64_be")]
+    be: Vec<i16>,
+    #[serde(with = "base64_le")]
+    floats: Vec<f64>,
+    #[serde(with = "base64_le")]
+    usizes: [usize; 2],
+    #[serde(with = "base64_be")]
+    isizes: Vec<isize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AlignmentTest {
+    #[serde(with = "base64")]
+    wide: Vec<u128>,
+    #[serde(with = "base64_if_readable")]
+    words: [u64; 3],
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LargeArrayTest {
+    #[serde(with = "base64")]
+    signature: [u8; 64],
+    #[serde(with = "base64_if_readable")]
+    block: [u32; 100],
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CompactTest {
+    #[serde(with = "base64_if_readable")]
+    words: Vec<u16>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LegacyTest {
+    #[serde(with = "base64_if_readable::legacy")]
+    words: Vec<u16>,
+    #[serde(with = "base64_if_readable::legacy::array")]
+    key: [u16; 2],
+}
+
+/// The binary format of [LegacyTest] before `base64_if_readable` wrote byte strings.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PreviousTest {
+    words: Vec<u16>,
+    key: [u16; 2],
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LenientTest {
+    #[serde(with = "base64::lenient")]
+    data: Vec<u8>,
+    #[serde(with = "base64::lenient")]
+    words: [u16; 2],
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StoredTest {
+    #[serde(with = "base64_if_readable")]
+    data: Vec<u8>,
+    words: [u16; 2],
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArrayTest {
+    #[serde(with = "base64::array")]
+    key: [u8; 32],
+    #[serde(with = "base64::array::With::<StandardNoPad>")]
+    words: [u64; 3],
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OwnedTest {
+    #[serde(with = "base64::owned")]
+    vec: Vec<u32>,
+    #[serde(with = "base64::owned")]
+    boxed: Box<[u8]>,
+    #[serde(with = "base64::owned")]
+    arc: std::sync::Arc<[u16]>,
+    #[serde(with = "base64::owned")]
+    bytes: bytes::Bytes,
+    #[serde(with = "base64::owned")]
+    array: [u8; 3],
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WrapperTest {
+    keys: Vec<Base64<[u8; 4]>>,
+    maybe: Option<Base64<Vec<u8>>>,
+    words: Base64<Vec<u16>, Standard, u16>,
+    name: Base64String<String, UrlSafeNoPad>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CombinatorTest {
+    #[serde(with = "base64::option")]
+    some: Option<Vec<u8>>,
+    #[serde(with = "base64::option::With::<StandardNoPad>")]
+    none: Option<[u8; 4]>,
+    #[serde(with = "base64::seq")]
+    keys: Vec<[u8; 4]>,
+    #[serde(with = "base64::seq")]
+    set: BTreeSet<[u16; 2]>,
+    #[serde(with = "base64::map_values")]
+    map: HashMap<String, Vec<u8>>,
+    #[serde(with = "base64_if_readable::seq")]
+    compact: Vec<Vec<u16>>,
+    #[serde(with = "base64_if_readable::map_values::With::<Standard>")]
+    compact_map: BTreeMap<u8, [u32; 1]>,
+    #[serde(with = "base64_if_readable::option")]
+    compact_option: Option<Vec<u8>>,
+    #[serde(with = "base64_string::option")]
+    name: Option<String>,
+    #[serde(with = "base64_string::seq")]
+    names: Vec<String>,
+    #[serde(with = "base64_string::map_values::With::<UrlSafeNoPad>")]
+    labels: BTreeMap<u32, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MapKeysTest {
+    #[serde(with = "base64::map_keys")]
+    records: HashMap<[u8; 4], String>,
+    #[serde(with = "base64::map_keys::With::<StandardNoPad>")]
+    words: BTreeMap<Vec<u16>, u32>,
+    wrapped: BTreeMap<Base64Key<[u8; 2]>, Vec<u8>>,
+}
+
+/// Upper case hex, to test the generic adaptors with a codec outside of the crate.
+pub struct Hex;
+
+impl Codec for Hex {
+    fn encoded_len(len: usize) -> Option<usize> {
+        Some(len * 2)
+    }
+
+    fn decoded_len_estimate(input: &[u8]) -> usize {
+        input.len() / 2
+    }
+
+    fn decoded_len(input: &[u8]) -> Option<usize> {
+        Some(input.len() / 2)
+    }
+
+    fn validate(bytes: &[u8]) -> Result<(), EncodeError> {
+        if bytes.len() > 256 {
+            return Err(EncodeError::TooLong {
+                len: bytes.len() * 2,
+                max: 512,
+            });
+        }
+        Ok(())
+    }
+
+    fn encode<W: std::fmt::Write>(bytes: &[u8], out: &mut W) -> std::fmt::Result {
+        bytes.iter().try_for_each(|byte| write!(out, "{byte:02X}"))
+    }
+
+    fn decode(input: &[u8], output: &mut [u8]) -> Result<usize, DecodeError> {
+        let len = input.len() / 2;
+        if input.len() % 2 != 0 || len > output.len() {
+            return Err(DecodeError::InvalidEncodedLength { len: input.len() });
+        }
+        for (index, pair) in input.chunks(2).enumerate() {
+            output[index] = std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or(DecodeError::InvalidSymbol { offset: index * 2 })?;
+        }
+        Ok(len)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CodecTest {
+    #[serde(with = "codec::bytes::With::<Hex>")]
+    bytes: Vec<u8>,
+    #[serde(with = "codec::bytes::array::With::<Hex>")]
+    words: [u16; 2],
+    #[serde(with = "codec::bytes_if_readable::seq::With::<Hex>")]
+    keys: Vec<[u8; 2]>,
+    #[serde(with = "codec::string::With::<Hex>")]
+    name: String,
+    #[serde(with = "base64::With::<Hex>")]
+    reexported: Vec<u8>,
+    wrapped: Base64<[u8; 2], Hex>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HexTest {
+    #[serde(with = "hex")]
+    bytes: Vec<u8>,
+    #[serde(with = "hex::With::<hex::Upper>")]
+    words: [u16; 2],
+    #[serde(with = "hex::array::With::<hex::PrefixedLower>")]
+    address: [u8; 4],
+    #[serde(with = "hex_if_readable::With::<hex::PrefixedUpper>")]
+    hash: Vec<u8>,
+    #[serde(with = "hex_if_readable::option")]
+    maybe: Option<[u8; 1]>,
+    #[serde(with = "hex_string")]
+    name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Base32Test {
+    #[serde(with = "base32")]
+    bytes: Vec<u8>,
+    #[serde(with = "base32::With::<base32::Rfc4648NoPad>")]
+    secret: [u8; 10],
+    #[serde(with = "base32::array::With::<base32::Crockford>")]
+    code: [u8; 5],
+    #[serde(with = "base32_if_readable::With::<base32::HexNoPad>")]
+    words: Vec<u16>,
+    #[serde(with = "base32_string::With::<base32::Hex>")]
+    name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Base85Test {
+    #[serde(with = "z85")]
+    bytes: Vec<u8>,
+    #[serde(with = "z85")]
+    vector: Vec<f32>,
+    #[serde(with = "z85::array")]
+    tail: [u8; 3],
+    #[serde(with = "ascii85")]
+    text: Vec<u8>,
+    #[serde(with = "ascii85::map_values")]
+    values: BTreeMap<String, [u8; 6]>,
+}
+
 fn assert_round_trips<A: PartialEq + Serialize + DeserializeOwned + Debug>(a: A) {
     let b = serde_json::to_string(&a).unwrap();
     let b: A = serde_json::from_str(&b).unwrap();
@@ -60,3 +312,732 @@ pub fn test() {
         bytes2: vec![123, 12, 84, 2],
     });
 }
+
+#[test]
+pub fn test_engines() {
+    let value = EngineTest {
+        standard: [251, 255, 191, 0],
+        standard_no_pad: vec![251, 255, 191, 0],
+        url_safe_no_pad: "\u{fb}".into(),
+    };
+    assert_eq!(
+        serde_json::to_string(&value).unwrap(),
+        r#"{"standard":"+/+/AA==","standard_no_pad":"+/+/AA","url_safe_no_pad":"w7s"}"#
+    );
+    assert_round_trips(value);
+}
+
+#[test]
+pub fn test_endian() {
+    let value = EndianTest {
+        le: [1, 0x01020304],
+        be: vec![1, -2],
+        floats: vec![1.5, -0.25],
+        usizes: [1, 2],
+        isizes: vec![-1],
+    };
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json["le"], "AQAAAAQDAgE=");
+    assert_eq!(json["be"], "AAH__g==");
+    assert_eq!(json["usizes"], "AQAAAAAAAAACAAAAAAAAAA==");
+    assert_eq!(json["isizes"], "__________8=");
+    assert_round_trips(value);
+}
+
+#[test]
+pub fn test_alignment() {
+    for i in 0..64 {
+        assert_round_trips(AlignmentTest {
+            wide: (0..i).map(|x| u128::MAX / 64 * x).collect(),
+            words: [i as u64, u64::MAX - i as u64, 1 << (i % 64)],
+        });
+    }
+    assert!(serde_json::from_str::<AlignmentTest>(r#"{"wide":"AAAA","words":"AAAA"}"#).is_err());
+}
+
+#[test]
+pub fn test_large_arrays() {
+    let mut value = LargeArrayTest {
+        signature: [0; 64],
+        block: [0; 100],
+    };
+    value
+        .signature
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, x)| *x = i as u8);
+    value
+        .block
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, x)| *x = i as u32 * 1000);
+    assert_round_trips(value);
+}
+
+#[test]
+pub fn test_compact_bytes() {
+    let words = vec![1, 2, 0xFFFF];
+    let bytes = postcard::to_allocvec(&CompactTest {
+        words: words.clone(),
+    })
+    .unwrap();
+    let mut expected = vec![6];
+    expected.extend(words.iter().flat_map(|x: &u16| x.to_ne_bytes()));
+    assert_eq!(bytes, expected);
+    assert_round_trips(CompactTest {
+        words: words.clone(),
+    });
+
+    let legacy = LegacyTest {
+        words: words.clone(),
+        key: [0x101, 0x202],
+    };
+    assert_eq!(serde_json::to_value(&legacy).unwrap()["key"], "AQECAg==");
+    let bytes = postcard::to_allocvec(&legacy).unwrap();
+    // Arrays are written as a tuple, without a length.
+    assert_eq!(bytes, [3, 1, 2, 0xFF, 0xFF, 0x03, 0x81, 0x02, 0x82, 0x04]);
+    assert_round_trips(legacy);
+
+    // Data written by previous versions is read by the legacy modules, and written unchanged.
+    let previous = PreviousTest {
+        words: words.clone(),
+        key: [0x101, 0x202],
+    };
+    let bytes = postcard::to_allocvec(&previous).unwrap();
+    let legacy: LegacyTest = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(legacy.words, previous.words);
+    assert_eq!(legacy.key, previous.key);
+    assert_eq!(postcard::to_allocvec(&legacy).unwrap(), bytes);
+}
+
+#[test]
+pub fn test_lenient() {
+    let expected = LenientTest {
+        data: vec![1, 2, 3],
+        words: [1, 2],
+    };
+    let words = serde_json::to_string(&base64_words(&[1, 2])).unwrap();
+    for json in [
+        r#"{"data":[1,2,3],"words":[1,2]}"#.to_owned(),
+        format!(r#"{{"data":"AQID","words":{words}}}"#),
+    ] {
+        assert_eq!(
+            serde_json::from_str::<LenientTest>(&json).unwrap(),
+            expected
+        );
+    }
+
+    // Only `lenient` reads sequences.
+    assert!(base64::deserialize::<_, Vec<u8>, u8>(serde_json::json!([1, 2, 3])).is_err());
+
+    let mut cbor = Vec::new();
+    ciborium::into_writer(
+        &StoredTest {
+            data: vec![1, 2, 3],
+            words: [1, 2],
+        },
+        &mut cbor,
+    )
+    .unwrap();
+    assert_eq!(
+        ciborium::from_reader::<LenientTest, _>(cbor.as_slice()).unwrap(),
+        expected
+    );
+
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&expected, &mut cbor).unwrap();
+    assert_eq!(
+        ciborium::from_reader::<LenientTest, _>(cbor.as_slice()).unwrap(),
+        expected
+    );
+}
+
+fn base64_words(words: &[u16]) -> String {
+    #[derive(Serialize)]
+    struct Words<'t>(#[serde(with = "base64")] &'t [u16]);
+    serde_json::to_value(Words(words))
+        .unwrap()
+        .as_str()
+        .unwrap()
+        .to_owned()
+}
+
+#[test]
+pub fn test_large_payload() {
+    use ::base64::{engine::general_purpose::URL_SAFE, Engine};
+
+    let data: Vec<u8> = (0..1 << 20).map(|x: u32| (x * 7) as u8).collect();
+    let value = BytesTest {
+        byte_array: [0; 2],
+        bytes: data.clone(),
+        byte_array2: [0; 2],
+        bytes2: data.clone(),
+    };
+    let mut json = Vec::new();
+    serde_json::to_writer(&mut json, &value).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["bytes"], URL_SAFE.encode(&data));
+    assert_eq!(json["bytes2"], URL_SAFE.encode(&data));
+    assert_round_trips(value);
+}
+
+#[test]
+pub fn test_array() {
+    let value = ArrayTest {
+        key: core::array::from_fn(|i| i as u8),
+        words: [1, u64::MAX, 3],
+    };
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json["key"], "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=");
+    assert_round_trips(value);
+
+    let message = serde_json::from_str::<ArrayTest>(
+        r#"{"key":"AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHg==","words":""}"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(
+        message.starts_with("expected 32 elements, got 31"),
+        "{message}"
+    );
+    let message = serde_json::from_str::<ArrayTest>(
+        r#"{"key":"AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=","words":"AAAA"}"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(
+        message.starts_with("decoded length 3 is not a multiple of element size 8"),
+        "{message}"
+    );
+}
+
+#[test]
+pub fn test_owned() {
+    let value = OwnedTest {
+        vec: vec![1, 2, 3],
+        boxed: Box::new([4, 5]),
+        arc: [6, 7].into(),
+        bytes: bytes::Bytes::from_static(b"bytes"),
+        array: [8, 9, 10],
+    };
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json["bytes"], "Ynl0ZXM=");
+    assert_round_trips(value);
+
+    let message = serde_json::from_str::<OwnedTest>(
+        r#"{"vec":"","boxed":"","arc":"","bytes":"","array":"AAAAAA=="}"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(
+        message.starts_with("conversion failed: cannot convert 4 elements into [u8; 3]"),
+        "{message}"
+    );
+}
+
+#[test]
+pub fn test_wrapper() {
+    let value = WrapperTest {
+        keys: vec![Base64::new([1, 2, 3, 4]), [5, 6, 7, 8].into()],
+        maybe: Some(Base64::new(b"bytes".to_vec())),
+        words: Base64::new(vec![0xfbff]),
+        name: Base64String::new("Hello".into()),
+    };
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json["keys"][0], "AQIDBA==");
+    assert_eq!(json["maybe"], "Ynl0ZXM=");
+    assert_eq!(json["words"], "//s=");
+    assert_eq!(json["name"], "SGVsbG8");
+    assert_round_trips(value);
+
+    let key: Base64<[u8; 4]> = "AQIDBA==".parse().unwrap();
+    assert_eq!(*key, [1, 2, 3, 4]);
+    assert_eq!(key.to_string(), "AQIDBA==");
+    assert_eq!(format!("{key:?}"), r#"Base64("AQIDBA==")"#);
+    let name: Base64String<String> = "SGVsbG8=".parse().unwrap();
+    assert_eq!(name.as_str(), "Hello");
+    assert_eq!(format!("{name:?}"), r#"Base64String("SGVsbG8=")"#);
+
+    let error = "AQID".parse::<Base64<[u8; 4]>>().unwrap_err();
+    assert!(
+        error.to_string().starts_with("conversion failed"),
+        "{error}"
+    );
+}
+
+#[test]
+pub fn test_combinators() {
+    let value = CombinatorTest {
+        some: Some(b"bytes".to_vec()),
+        none: None,
+        keys: vec![[1, 2, 3, 4], [5, 6, 7, 8]],
+        set: [[1, 2], [3, 4]].into(),
+        map: [("a".into(), vec![1]), ("b".into(), vec![])].into(),
+        compact: vec![vec![1, 2], vec![]],
+        compact_map: [(1, [7])].into(),
+        compact_option: Some(vec![1, 2, 3]),
+        name: Some("Hello".into()),
+        names: vec!["a".into(), "bc".into()],
+        labels: [(1, "Hello".into())].into(),
+    };
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json["some"], "Ynl0ZXM=");
+    assert_eq!(json["none"], serde_json::Value::Null);
+    assert_eq!(json["keys"], serde_json::json!(["AQIDBA==", "BQYHCA=="]));
+    assert_eq!(json["map"]["a"], "AQ==");
+    assert_eq!(json["map"]["b"], "");
+    assert_eq!(json["name"], "SGVsbG8=");
+    assert_eq!(json["names"], serde_json::json!(["YQ==", "YmM="]));
+    assert_eq!(json["labels"]["1"], "SGVsbG8");
+    assert_round_trips(value);
+
+    let message = base64::seq::deserialize::<_, Vec<[u8; 4]>, [u8; 4], u8>(serde_json::json!([
+        "AQIDBA==", "AQ=="
+    ]))
+    .unwrap_err()
+    .to_string();
+    assert!(message.starts_with("conversion failed"), "{message}");
+}
+
+#[test]
+pub fn test_map_keys() {
+    let value = MapKeysTest {
+        records: [([1, 2, 3, 4], "record".into())].into(),
+        words: [(vec![0xffff], 1), (vec![], 2)].into(),
+        wrapped: [(Base64Key::new([1, 2]), vec![3])].into(),
+    };
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json["records"]["AQIDBA=="], "record");
+    assert_eq!(json["words"]["//8"], 1);
+    assert_eq!(json["words"][""], 2);
+    assert_eq!(json["wrapped"]["AQI="], serde_json::json!([3]));
+    assert_round_trips(value);
+
+    // Binary formats use the representation of the key.
+    let key = Base64Key::<[u8; 2]>::new([1, 2]);
+    assert_eq!(postcard::to_allocvec(&key).unwrap(), [1, 2]);
+    assert_eq!(key.to_string(), "AQI=");
+    assert_eq!(format!("{key:?}"), r#"Base64Key("AQI=")"#);
+    assert_eq!("AQI=".parse::<Base64Key<[u8; 2]>>().unwrap(), key);
+
+    let message = serde_json::from_str::<MapKeysTest>(
+        r#"{"records":{"AQID":"record"},"words":{},"wrapped":{}}"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(message.starts_with("conversion failed"), "{message}");
+}
+
+#[test]
+pub fn test_codec() {
+    let value = CodecTest {
+        bytes: vec![0xab, 0xcd],
+        words: [0x0102, 0x0304],
+        keys: vec![[0xff, 0]],
+        name: "Hi".into(),
+        reexported: vec![1],
+        wrapped: Base64::new([2, 3]),
+    };
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json["bytes"], "ABCD");
+    assert_eq!(json["words"], "02010403");
+    assert_eq!(json["keys"], serde_json::json!(["FF00"]));
+    assert_eq!(json["name"], "4869");
+    assert_eq!(json["reexported"], "01");
+    assert_eq!(json["wrapped"], "0203");
+    assert_round_trips(value);
+
+    let message = serde_json::from_str::<CodecTest>(
+        r#"{"bytes":"ABCG","words":"","keys":[],"name":"","reexported":"","wrapped":""}"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(
+        message.starts_with("invalid symbol at offset 2"),
+        "{message}"
+    );
+    let message = serde_json::from_str::<CodecTest>(
+        r#"{"bytes":"","words":"0201","keys":[],"name":"","reexported":"","wrapped":""}"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(
+        message.starts_with("expected 2 elements, got 1"),
+        "{message}"
+    );
+
+    // Inputs rejected by the codec are reported as errors by every adaptor.
+    let long = vec![0u8; 300];
+    let message = codec::bytes::With::<Hex>::serialize(&long, serde_json::value::Serializer)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(message, "encoded length 600 exceeds the maximum of 512");
+    let long = "a".repeat(300);
+    assert!(codec::string::With::<Hex>::serialize(&long, serde_json::value::Serializer).is_err());
+    assert!(serde_json::to_string(&CodecTest {
+        bytes: vec![0; 300],
+        words: [0, 0],
+        keys: vec![],
+        name: String::new(),
+        reexported: vec![],
+        wrapped: Base64::new([0, 0]),
+    })
+    .is_err());
+
+    for len in 0..10 {
+        let data = vec![0; len];
+        assert_eq!(
+            Standard::encoded_len(len),
+            Some(::base64::Engine::encode(&Standard::ENGINE, &data).len())
+        );
+        assert_eq!(
+            UrlSafeNoPad::encoded_len(len),
+            Some(::base64::Engine::encode(&UrlSafeNoPad::ENGINE, &data).len())
+        );
+    }
+}
+
+#[test]
+pub fn test_hex() {
+    let value = HexTest {
+        bytes: vec![0x01, 0xab],
+        words: [0x0102, 0xabcd],
+        address: [0xde, 0xad, 0xbe, 0xef],
+        hash: vec![0xca, 0xfe],
+        maybe: Some([0x0f]),
+        name: "Hi".into(),
+    };
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json["bytes"], "01ab");
+    assert_eq!(
+        json["words"],
+        if cfg!(target_endian = "little") {
+            "0201CDAB"
+        } else {
+            "0102ABCD"
+        }
+    );
+    assert_eq!(json["address"], "0xdeadbeef");
+    assert_eq!(json["hash"], "0xCAFE");
+    assert_eq!(json["maybe"], "0f");
+    assert_eq!(json["name"], "4869");
+    let bytes = postcard::to_allocvec(&value).unwrap();
+    assert!(bytes.windows(3).any(|x| x == [2, 0xca, 0xfe]));
+    assert_round_trips(value);
+
+    // Both cases are accepted, and the prefix is optional for prefixed formats.
+    let value: HexTest = serde_json::from_str(
+        r#"{"bytes":"01AB","words":"0000ffff","address":"DEADBEEF","hash":"0Xcafe","maybe":null,"name":"4869"}"#,
+    )
+    .unwrap();
+    assert_eq!(value.bytes, [0x01, 0xab]);
+    assert_eq!(value.words, [0, 0xffff]);
+    assert_eq!(value.address, [0xde, 0xad, 0xbe, 0xef]);
+    assert_eq!(value.hash, [0xca, 0xfe]);
+
+    #[derive(Debug, Deserialize)]
+    struct Error {
+        #[serde(with = "hex")]
+        _bytes: Vec<u8>,
+        #[serde(with = "hex::array::With::<hex::PrefixedLower>")]
+        _address: [u8; 2],
+    }
+    let error = |json: &str| serde_json::from_str::<Error>(json).unwrap_err().to_string();
+    let message = error(r#"{"_bytes":"0x01","_address":"0x0102"}"#);
+    assert!(
+        message.starts_with("invalid symbol at offset 1"),
+        "{message}"
+    );
+    let message = error(r#"{"_bytes":"012","_address":"0x0102"}"#);
+    assert!(message.starts_with("invalid encoded length 3"), "{message}");
+    let message = error(r#"{"_bytes":"","_address":"0x01g2"}"#);
+    assert!(
+        message.starts_with("invalid symbol at offset 4"),
+        "{message}"
+    );
+    let message = error(r#"{"_bytes":"","_address":"0x010203"}"#);
+    assert!(
+        message.starts_with("expected 2 elements, got 3"),
+        "{message}"
+    );
+}
+
+#[test]
+pub fn test_base32() {
+    // Test vectors from RFC 4648.
+    let vectors: [(&str, &str, &str); 7] = [
+        ("", "", ""),
+        ("f", "MY======", "CO======"),
+        ("fo", "MZXQ====", "CPNG===="),
+        ("foo", "MZXW6===", "CPNMU==="),
+        ("foob", "MZXW6YQ=", "CPNMUOG="),
+        ("fooba", "MZXW6YTB", "CPNMUOJ1"),
+        ("foobar", "MZXW6YTBOI======", "CPNMUOJ1E8======"),
+    ];
+    for (text, rfc4648, hex) in vectors {
+        let encoded = base32_string::serialize(&text, serde_json::value::Serializer).unwrap();
+        assert_eq!(encoded, rfc4648);
+        let decoded: String = base32_string::deserialize(encoded).unwrap();
+        assert_eq!(decoded, text);
+        let encoded =
+            base32_string::With::<base32::Hex>::serialize(&text, serde_json::value::Serializer)
+                .unwrap();
+        assert_eq!(encoded, hex);
+        let encoded = base32_string::With::<base32::HexNoPad>::serialize(
+            &text,
+            serde_json::value::Serializer,
+        )
+        .unwrap();
+        assert_eq!(encoded, hex.trim_end_matches('='));
+    }
+
+    let value = Base32Test {
+        bytes: b"foobar".to_vec(),
+        secret: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+        code: [1, 2, 3, 4, 5],
+        words: vec![1, 2, 3],
+        name: "Hi".into(),
+    };
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json["bytes"], "MZXW6YTBOI======");
+    assert_eq!(json["secret"], "AEBAGBAFAYDQQCIK");
+    assert_eq!(json["code"], "04106105");
+    assert_round_trips(value);
+
+    // Crockford is case insensitive, ignores hyphens and reads I, L and O as digits.
+    let code: [u8; 5] =
+        base32::array::With::<base32::Crockford>::deserialize(serde_json::json!("o41-o6io5"))
+            .unwrap();
+    assert_eq!(code, [1, 2, 3, 4, 5]);
+    let bytes: Vec<u8> = base32::deserialize(serde_json::json!("mzxw6ytboi======")).unwrap();
+    assert_eq!(bytes, b"foobar");
+
+    let error = |json: serde_json::Value| {
+        base32::deserialize::<_, Vec<u8>, u8>(json)
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(error("MZXW6YTBOI".into()), "invalid padding");
+    assert_eq!(error("MZXW6YTBOI=====".into()), "invalid padding");
+    assert_eq!(error("MZXW6Y==".into()), "invalid encoded length 8");
+    assert_eq!(error("MZXW6Y1B".into()), "invalid symbol at offset 6");
+    // Non-zero unused bits in the final symbol.
+    assert_eq!(error("MZ======".into()), "invalid symbol at offset 1");
+    let error = base32::With::<base32::Rfc4648NoPad>::deserialize::<_, Vec<u8>, u8>(
+        serde_json::json!("MY======"),
+    )
+    .unwrap_err();
+    assert_eq!(error.to_string(), "invalid padding");
+}
+
+#[test]
+pub fn test_base85() {
+    let value = Base85Test {
+        // Test vector from the Z85 specification.
+        bytes: vec![0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B],
+        vector: vec![1.0, -0.5, f32::MAX],
+        tail: [0xff, 0xff, 0xff],
+        text: b"Man is distinguished".to_vec(),
+        values: BTreeMap::from([("a".into(), [0, 0, 0, 0, b'a', b'b'])]),
+    };
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json["bytes"], "HelloWorld");
+    assert_eq!(json["tail"], "%nS9");
+    assert_eq!(json["text"], "9jqo^BlbD-BleB1DJ+*+F(f,q");
+    assert_eq!(json["values"]["a"], "z@:B");
+    assert_round_trips(value);
+
+    for len in 0..12 {
+        let data: Vec<u8> = (0..len).map(|x: u8| x.wrapping_mul(37)).collect();
+        let encoded = z85::serialize(&data, serde_json::value::Serializer).unwrap();
+        assert_eq!(
+            encoded.as_str().unwrap().len(),
+            len as usize / 4 * 5 + [0, 2, 3, 4][len as usize % 4]
+        );
+        let decoded: Vec<u8> = z85::deserialize(encoded).unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    let error = |json: &str| {
+        z85::deserialize::<_, Vec<u8>, u8>(serde_json::json!(json))
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(error("HelloW"), "invalid encoded length 6");
+    assert_eq!(error("Hello\\"), "invalid symbol at offset 5");
+    assert_eq!(error("#####"), "invalid symbol at offset 0");
+    let error = ascii85::deserialize::<_, Vec<u8>, u8>(serde_json::json!("9jzqo")).unwrap_err();
+    assert_eq!(error.to_string(), "invalid symbol at offset 2");
+    let error = z85::array::deserialize::<_, u8, 3>(serde_json::json!("HelloWorld")).unwrap_err();
+    assert_eq!(error.to_string(), "expected 3 elements, got 8");
+}
+
+#[test]
+pub fn test_base45() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Payload {
+        #[serde(with = "base45")]
+        bytes: Vec<u8>,
+        #[serde(with = "base45::array")]
+        words: [u16; 2],
+    }
+
+    // Test vectors from RFC 9285.
+    for (text, encoded) in [
+        ("", ""),
+        ("AB", "BB8"),
+        ("Hello!!", "%69 VD92EX0"),
+        ("base-45", "UJCLQE7W581"),
+        ("ietf!", "QED8WEX0"),
+    ] {
+        let value = base45::serialize(&text.as_bytes(), serde_json::value::Serializer).unwrap();
+        assert_eq!(value, encoded);
+        let decoded: Vec<u8> = base45::deserialize(value).unwrap();
+        assert_eq!(decoded, text.as_bytes());
+    }
+    assert_round_trips(Payload {
+        bytes: vec![0, 255, 1],
+        words: [u16::MAX, 0],
+    });
+
+    let error = |json: &str| {
+        base45::deserialize::<_, Vec<u8>, u8>(serde_json::json!(json))
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(error("GGW"), "invalid symbol at offset 0");
+    assert_eq!(error("BB8:::"), "invalid symbol at offset 3");
+    assert_eq!(error("BB8a"), "invalid encoded length 4");
+    assert_eq!(error("BB8aB"), "invalid symbol at offset 3");
+}
+
+#[test]
+pub fn test_multibase() {
+    use multibase::Multibase;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Content {
+        #[serde(with = "multibase")]
+        default: Vec<u8>,
+        #[serde(with = "multibase::With::<Multibase<base32::Rfc4648NoPad>>")]
+        base32: Vec<u8>,
+        #[serde(with = "multibase::array::With::<Multibase<hex::Upper>>")]
+        hex: [u8; 2],
+        #[serde(with = "multibase::option::With::<Multibase<Standard>>")]
+        base64: Option<Vec<u8>>,
+    }
+
+    let value = Content {
+        default: b"yes mani !".to_vec(),
+        base32: b"yes mani !".to_vec(),
+        hex: [0xab, 0xcd],
+        base64: Some(b"yes mani !".to_vec()),
+    };
+    let json = serde_json::to_value(&value).unwrap();
+    // Test vectors from the multibase specification.
+    assert_eq!(json["default"], "ueWVzIG1hbmkgIQ");
+    assert_eq!(json["base32"], "BPFSXGIDNMFXGSIBB");
+    assert_eq!(json["hex"], "FABCD");
+    assert_eq!(json["base64"], "MeWVzIG1hbmkgIQ==");
+    assert_round_trips(value);
+
+    // Every supported encoding is accepted regardless of the written one.
+    for encoded in [
+        "f796573206d616e692021",
+        "F796573206D616E692021",
+        "bpfsxgidnmfxgsibb",
+        "BPFSXGIDNMFXGSIBB",
+        "cpfsxgidnmfxgsibb",
+        "vf5in683dc5n6i811",
+        "Vf5in683dc5n6i811",
+        "meWVzIG1hbmkgIQ",
+        "MeWVzIG1hbmkgIQ==",
+        "ueWVzIG1hbmkgIQ",
+        "UeWVzIG1hbmkgIQ==",
+        "RRFF.OEB$D5/DZ24",
+    ] {
+        let decoded: Vec<u8> = multibase::deserialize(serde_json::json!(encoded)).unwrap();
+        assert_eq!(decoded, b"yes mani !", "{encoded}");
+    }
+
+    let error = |json: &str| {
+        multibase::deserialize::<_, Vec<u8>, u8>(serde_json::json!(json))
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(error(""), "invalid prefix");
+    assert_eq!(error("xeWVz"), "invalid prefix");
+    assert_eq!(error("ueW!z"), "invalid symbol at offset 3");
+    assert_eq!(error("f012"), "invalid encoded length 4");
+    let error = multibase::array::deserialize::<_, u8, 3>(serde_json::json!("f0102")).unwrap_err();
+    assert_eq!(error.to_string(), "expected 3 elements, got 2");
+}
+
+fn assert_matches_engine<E: serde_repr_base64::Base64Engine>(data: &[u8]) {
+    use ::base64::Engine;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(bound = "")]
+    struct Data<E: serde_repr_base64::Base64Engine>(
+        #[serde(with = "base64::With::<E>")] Vec<u8>,
+        std::marker::PhantomData<E>,
+    );
+
+    let json = serde_json::to_value(Data::<E>(data.to_vec(), std::marker::PhantomData)).unwrap();
+    assert_eq!(json[0], E::ENGINE.encode(data));
+    let decoded: Data<E> = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded.0, data);
+}
+
+#[test]
+pub fn test_backend() {
+    for len in [0, 1, 2, 3, 4, 100, 3071, 3072, 3073, 10000] {
+        let data: Vec<u8> = (0..len).map(|x: u32| (x * 31 + 7) as u8).collect();
+        assert_matches_engine::<Standard>(&data);
+        assert_matches_engine::<StandardNoPad>(&data);
+        assert_matches_engine::<UrlSafe>(&data);
+        assert_matches_engine::<UrlSafeNoPad>(&data);
+    }
+}
+
+#[test]
+pub fn test_errors() {
+    let error = |json: &str| {
+        serde_json::from_str::<BytesTest>(json)
+            .unwrap_err()
+            .to_string()
+    };
+    let message = error(r#"{"byte_array":"c2Vj$mV0","bytes":"","byte_array2":"","bytes2":""}"#);
+    assert!(
+        message.starts_with("invalid symbol at offset 4"),
+        "{message}"
+    );
+    assert!(!message.contains("c2Vj"), "{message}");
+    let message = error(r#"{"byte_array":"AAA","bytes":"","byte_array2":"","bytes2":""}"#);
+    assert!(message.starts_with("invalid padding"), "{message}");
+    let message = error(r#"{"byte_array":"AAAAAA==","bytes":"","byte_array2":"","bytes2":""}"#);
+    assert!(
+        message.starts_with(
+            "conversion failed: cannot convert 4 elements into [u8; 2]: could not convert slice to array"
+        ),
+        "{message}"
+    );
+
+    let message = serde_json::from_str::<EndianTest>(
+        r#"{"le":"AAA=","be":"","floats":"","usizes":"","isizes":""}"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(
+        message.starts_with("decoded length 2 is not a multiple of element size 4"),
+        "{message}"
+    );
+
+    let message = serde_json::from_str::<StringTest>(r#"{"str":"_w=="}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        message.starts_with("decoded bytes are not valid utf-8 at offset 0"),
+        "{message}"
+    );
+}